@@ -1,15 +1,23 @@
 use std::fmt;
 
-use crate::{Error, ErrorKind, Result as ColorResult, Solid, gradient::Gradient, parse};
+#[cfg(feature = "schema")]
+use schema_jsonrs::{JsonSchema, Schema, SchemaGenerator, json_schema};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Visitor};
+#[cfg(feature = "schema")]
+use std::borrow::Cow;
+
+use crate::{
+    ColorScheme, Error, ErrorKind, ParseOptions, Result as ColorResult, Solid, gradient::Gradient,
+    parse, parse_with_options,
+};
 
-#[cfg_attr(feature = "schema", derive(schema_jsonrs::JsonSchema))]
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub enum ColorValue {
     Solid(Solid),
     Gradient(Gradient),
 }
 
-#[cfg_attr(feature = "schema", derive(schema_jsonrs::JsonSchema))]
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct Color(pub ColorValue);
 
@@ -25,6 +33,28 @@ impl Color {
         Self::try_from_theme(s.as_ref(), path.as_ref())
     }
 
+    /// Create color from CSS color string, using the given parse options.
+    pub fn from_html_with_options<S: AsRef<str>>(
+        s: S,
+        options: &ParseOptions,
+    ) -> ColorResult<Self> {
+        parse_with_options(s.as_ref(), options)
+    }
+
+    /// Create color from CSS color string, resolving `light-dark()` for the given color scheme.
+    pub fn from_html_with_color_scheme<S: AsRef<str>>(
+        s: S,
+        color_scheme: ColorScheme,
+    ) -> ColorResult<Self> {
+        parse_with_options(s.as_ref(), &ParseOptions::with_color_scheme(color_scheme))
+    }
+
+    /// The color as a CSS string, which parses back to the same color, see
+    /// [`ColorValue::to_css_string`].
+    pub fn to_css_string(&self) -> String {
+        self.0.to_css_string()
+    }
+
     /// Convert a `Color` to a `Gradient`.
     pub fn to_gradient(&self) -> ColorResult<Gradient> {
         match self.0.clone() {
@@ -71,6 +101,17 @@ impl TryFrom<&str> for Color {
     }
 }
 
+impl ColorValue {
+    /// The color as a CSS string, which parses back to the same color, see
+    /// [`Solid::to_css_string`] and [`Gradient::to_css_string`].
+    pub fn to_css_string(&self) -> String {
+        match self {
+            ColorValue::Solid(solid) => solid.to_css_string(),
+            ColorValue::Gradient(gradient) => gradient.to_css_string(),
+        }
+    }
+}
+
 impl fmt::Display for ColorValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -85,3 +126,84 @@ impl fmt::Display for Color {
         write!(f, "Color({})", self.0)
     }
 }
+
+/// Implement Serde serialization into a CSS color or gradient string
+#[cfg(feature = "serde")]
+impl Serialize for ColorValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_css_string())
+    }
+}
+
+/// Implement Serde deserialization from string
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ColorValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Color::deserialize(deserializer).map(|color| color.0)
+    }
+}
+
+/// Implement Serde serialization into a CSS color or gradient string
+#[cfg(feature = "serde")]
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+/// Implement Serde deserialization from string
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(ColorVisitor)
+    }
+}
+
+#[cfg(feature = "serde")]
+struct ColorVisitor;
+
+#[cfg(feature = "serde")]
+impl Visitor<'_> for ColorVisitor {
+    type Value = Color;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a valid css color or gradient")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Color::try_from(v).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Describe the CSS string that Serde (de)serializes as a JSON Schema string
+#[cfg(feature = "schema")]
+impl JsonSchema for ColorValue {
+    fn schema_name() -> Cow<'static, str> {
+        "ColorValue".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "A CSS color or gradient, e.g. `#ff0000` or `linear-gradient(#ff0000, #0000ff)`",
+        })
+    }
+}
+
+/// Describe the CSS string that Serde (de)serializes as a JSON Schema string
+#[cfg(feature = "schema")]
+impl JsonSchema for Color {
+    fn schema_name() -> Cow<'static, str> {
+        "Color".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "A CSS color or gradient, e.g. `#ff0000` or `linear-gradient(#ff0000, #0000ff)`",
+        })
+    }
+}
@@ -1,7 +1,162 @@
 use core::fmt;
 use std::ops::Mul;
 
-use crate::utils::{hsl_to_rgb, normalize_angle, rgb_to_hsl};
+use crate::Error;
+use crate::ErrorKind;
+use crate::utils::{
+    hsl_to_rgb, hwb_to_rgb, lab_to_rgb, lch_to_lab, normalize_angle, oklab_to_rgb, rgb_to_hsl,
+};
+
+/// The color spaces a [`Solid`](crate::Solid) can be converted from and to with
+/// [`Solid::from_colorspace`](crate::Solid::from_colorspace) and
+/// [`Solid::to_colorspace`](crate::Solid::to_colorspace), and that colors can be interpolated in.
+///
+/// Components of the RGB spaces are in the range [0..1]. The other spaces use the same units as
+/// the CSS functions of the same name: `hsl` is `[h, s, l]` with saturation and lightness in
+/// [0..100], `hwb` is `[h, w, b]` in [0..100], `lab` is `[l, a, b]` with lightness in [0..100],
+/// `lch` is `[l, c, h]`, and `oklab`/`oklch` use a lightness in [0..1].
+#[cfg_attr(feature = "schema", derive(schema_jsonrs::JsonSchema))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Colorspace {
+    /// `srgb` (the default)
+    #[default]
+    Srgb,
+    /// `srgb-linear`
+    SrgbLinear,
+    /// `display-p3`
+    DisplayP3,
+    /// `a98-rgb`
+    A98Rgb,
+    /// `prophoto-rgb`
+    ProphotoRgb,
+    /// `rec2020`
+    Rec2020,
+    /// `xyz-d50`
+    XyzD50,
+    /// `xyz-d65` (or `xyz`)
+    XyzD65,
+    /// `hsl`
+    Hsl,
+    /// `hwb`
+    Hwb,
+    /// `lab`
+    Lab,
+    /// `lch`
+    Lch,
+    /// `oklab`
+    Oklab,
+    /// `oklch`
+    Oklch,
+}
+
+/// The kinds of color components that CSS treats as
+/// [analogous](https://www.w3.org/TR/css-color-4/#interpolation-missing) across color spaces,
+/// so a component missing (`none`) in one space is also missing in the other.
+#[cfg_attr(feature = "schema", derive(schema_jsonrs::JsonSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorComponent {
+    /// `r` and `x`
+    Red,
+    /// `g` and `y`
+    Green,
+    /// `b` and `z`
+    Blue,
+    /// `l` of `hsl`, `lab`, `lch`, `oklab` and `oklch`
+    Lightness,
+    /// `s` of `hsl` and `c` of `lch` and `oklch`
+    Colorfulness,
+    /// `h` of `hsl`, `hwb`, `lch` and `oklch`
+    Hue,
+    /// `a` of `lab` and `oklab`
+    OpponentA,
+    /// `b` of `lab` and `oklab`
+    OpponentB,
+    /// The alpha channel of every color space.
+    Alpha,
+}
+
+impl Colorspace {
+    /// The name of the color space in CSS.
+    pub fn css_name(&self) -> &'static str {
+        match self {
+            Self::Srgb => "srgb",
+            Self::SrgbLinear => "srgb-linear",
+            Self::DisplayP3 => "display-p3",
+            Self::A98Rgb => "a98-rgb",
+            Self::ProphotoRgb => "prophoto-rgb",
+            Self::Rec2020 => "rec2020",
+            Self::XyzD50 => "xyz-d50",
+            Self::XyzD65 => "xyz-d65",
+            Self::Hsl => "hsl",
+            Self::Hwb => "hwb",
+            Self::Lab => "lab",
+            Self::Lch => "lch",
+            Self::Oklab => "oklab",
+            Self::Oklch => "oklch",
+        }
+    }
+
+    /// Whether this is one of the predefined color spaces accepted by the CSS `color()` function.
+    pub fn is_predefined(&self) -> bool {
+        !matches!(
+            self,
+            Self::Hsl | Self::Hwb | Self::Lab | Self::Lch | Self::Oklab | Self::Oklch
+        )
+    }
+
+    /// The analogous kind of each of the three components, `None` when a component has no
+    /// analogous component in other color spaces (e.g. whiteness and blackness of `hwb`).
+    pub fn components(&self) -> [Option<ColorComponent>; 3] {
+        use ColorComponent::*;
+
+        match self {
+            Self::Hsl => [Some(Hue), Some(Colorfulness), Some(Lightness)],
+            Self::Hwb => [Some(Hue), None, None],
+            Self::Lab | Self::Oklab => [Some(Lightness), Some(OpponentA), Some(OpponentB)],
+            Self::Lch | Self::Oklch => [Some(Lightness), Some(Colorfulness), Some(Hue)],
+            _ => [Some(Red), Some(Green), Some(Blue)],
+        }
+    }
+
+    /// The index of the hue component, for the polar color spaces.
+    pub fn hue_index(&self) -> Option<usize> {
+        match self {
+            Self::Hsl | Self::Hwb => Some(0),
+            Self::Lch | Self::Oklch => Some(2),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<&str> for Colorspace {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "srgb" => Ok(Self::Srgb),
+            "srgb-linear" => Ok(Self::SrgbLinear),
+            "display-p3" => Ok(Self::DisplayP3),
+            "a98-rgb" => Ok(Self::A98Rgb),
+            "prophoto-rgb" => Ok(Self::ProphotoRgb),
+            "rec2020" => Ok(Self::Rec2020),
+            "xyz-d50" => Ok(Self::XyzD50),
+            "xyz" | "xyz-d65" => Ok(Self::XyzD65),
+            "hsl" => Ok(Self::Hsl),
+            "hwb" => Ok(Self::Hwb),
+            "lab" => Ok(Self::Lab),
+            "lch" => Ok(Self::Lch),
+            "oklab" => Ok(Self::Oklab),
+            "oklch" => Ok(Self::Oklch),
+            _ => Err(Error::new(ErrorKind::InvalidColorspace, s)),
+        }
+    }
+}
+
+impl fmt::Display for Colorspace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.css_name())
+    }
+}
 
 pub trait ColorspaceImpl {
     fn to_rgba(&self) -> Rgba;
@@ -264,6 +419,209 @@ impl ColorspaceImpl for NormalizedHsla {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, PartialOrd, Copy)]
+pub struct Hwba {
+    pub h: f32,
+    pub w: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl ColorspaceImpl for Hwba {
+    fn to_rgba(&self) -> Rgba {
+        let (r, g, b) = hwb_to_rgb(normalize_angle(self.h), self.w / 100.0, self.b / 100.0);
+        Rgba {
+            r: (r * 255.0).round() as u8,
+            g: (g * 255.0).round() as u8,
+            b: (b * 255.0).round() as u8,
+            a: self.a,
+        }
+    }
+
+    fn to_normalized_rgba(&self) -> NormalizedRgba {
+        let (r, g, b) = hwb_to_rgb(normalize_angle(self.h), self.w / 100.0, self.b / 100.0);
+        NormalizedRgba { r, g, b, a: self.a }
+    }
+
+    fn to_rgba16(&self) -> Rgba16 {
+        let (r, g, b) = hwb_to_rgb(normalize_angle(self.h), self.w / 100.0, self.b / 100.0);
+        Rgba16 {
+            r: (r * 65535.0).round() as u16,
+            g: (g * 65535.0).round() as u16,
+            b: (b * 65535.0).round() as u16,
+            a: self.a,
+        }
+    }
+
+    fn to_normalized_hsla(&self) -> NormalizedHsla {
+        self.to_normalized_rgba().to_normalized_hsla()
+    }
+
+    fn to_hsla(&self) -> Hsla {
+        self.to_normalized_rgba().to_hsla()
+    }
+}
+
+/// CIE Lab color, relative to the D50 white point.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Copy)]
+pub struct Lab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+    pub alpha: f32,
+}
+
+impl ColorspaceImpl for Lab {
+    fn to_rgba(&self) -> Rgba {
+        self.to_normalized_rgba().to_rgba()
+    }
+
+    fn to_normalized_rgba(&self) -> NormalizedRgba {
+        let (r, g, b) = lab_to_rgb(self.l, self.a, self.b);
+        NormalizedRgba {
+            r: r.clamp(0.0, 1.0),
+            g: g.clamp(0.0, 1.0),
+            b: b.clamp(0.0, 1.0),
+            a: self.alpha,
+        }
+    }
+
+    fn to_rgba16(&self) -> Rgba16 {
+        self.to_normalized_rgba().to_rgba16()
+    }
+
+    fn to_normalized_hsla(&self) -> NormalizedHsla {
+        self.to_normalized_rgba().to_normalized_hsla()
+    }
+
+    fn to_hsla(&self) -> Hsla {
+        self.to_normalized_rgba().to_hsla()
+    }
+}
+
+/// CIE LCH color (the polar form of [`Lab`]), relative to the D50 white point.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Copy)]
+pub struct Lch {
+    pub l: f32,
+    pub c: f32,
+    pub h: f32,
+    pub alpha: f32,
+}
+
+impl Lch {
+    fn to_lab(self) -> Lab {
+        let (a, b) = lch_to_lab(self.c.max(0.0), normalize_angle(self.h));
+        Lab {
+            l: self.l,
+            a,
+            b,
+            alpha: self.alpha,
+        }
+    }
+}
+
+impl ColorspaceImpl for Lch {
+    fn to_rgba(&self) -> Rgba {
+        self.to_lab().to_rgba()
+    }
+
+    fn to_normalized_rgba(&self) -> NormalizedRgba {
+        self.to_lab().to_normalized_rgba()
+    }
+
+    fn to_rgba16(&self) -> Rgba16 {
+        self.to_lab().to_rgba16()
+    }
+
+    fn to_normalized_hsla(&self) -> NormalizedHsla {
+        self.to_lab().to_normalized_hsla()
+    }
+
+    fn to_hsla(&self) -> Hsla {
+        self.to_lab().to_hsla()
+    }
+}
+
+/// OKLab color.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Copy)]
+pub struct Oklab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+    pub alpha: f32,
+}
+
+impl ColorspaceImpl for Oklab {
+    fn to_rgba(&self) -> Rgba {
+        self.to_normalized_rgba().to_rgba()
+    }
+
+    fn to_normalized_rgba(&self) -> NormalizedRgba {
+        let (r, g, b) = oklab_to_rgb(self.l, self.a, self.b);
+        NormalizedRgba {
+            r: r.clamp(0.0, 1.0),
+            g: g.clamp(0.0, 1.0),
+            b: b.clamp(0.0, 1.0),
+            a: self.alpha,
+        }
+    }
+
+    fn to_rgba16(&self) -> Rgba16 {
+        self.to_normalized_rgba().to_rgba16()
+    }
+
+    fn to_normalized_hsla(&self) -> NormalizedHsla {
+        self.to_normalized_rgba().to_normalized_hsla()
+    }
+
+    fn to_hsla(&self) -> Hsla {
+        self.to_normalized_rgba().to_hsla()
+    }
+}
+
+/// OKLCH color (the polar form of [`Oklab`]).
+#[derive(Debug, Clone, PartialEq, PartialOrd, Copy)]
+pub struct Oklch {
+    pub l: f32,
+    pub c: f32,
+    pub h: f32,
+    pub alpha: f32,
+}
+
+impl Oklch {
+    fn to_oklab(self) -> Oklab {
+        let (a, b) = lch_to_lab(self.c.max(0.0), normalize_angle(self.h));
+        Oklab {
+            l: self.l,
+            a,
+            b,
+            alpha: self.alpha,
+        }
+    }
+}
+
+impl ColorspaceImpl for Oklch {
+    fn to_rgba(&self) -> Rgba {
+        self.to_oklab().to_rgba()
+    }
+
+    fn to_normalized_rgba(&self) -> NormalizedRgba {
+        self.to_oklab().to_normalized_rgba()
+    }
+
+    fn to_rgba16(&self) -> Rgba16 {
+        self.to_oklab().to_rgba16()
+    }
+
+    fn to_normalized_hsla(&self) -> NormalizedHsla {
+        self.to_oklab().to_normalized_hsla()
+    }
+
+    fn to_hsla(&self) -> Hsla {
+        self.to_oklab().to_hsla()
+    }
+}
+
 impl fmt::Display for Rgba {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -313,3 +671,41 @@ impl fmt::Display for NormalizedHsla {
         )
     }
 }
+
+impl fmt::Display for Hwba {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Hwba({}, {}, {}, {})", self.h, self.w, self.b, self.a)
+    }
+}
+
+impl fmt::Display for Lab {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Lab({}, {}, {}, {})", self.l, self.a, self.b, self.alpha)
+    }
+}
+
+impl fmt::Display for Lch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Lch({}, {}, {}, {})", self.l, self.c, self.h, self.alpha)
+    }
+}
+
+impl fmt::Display for Oklab {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Oklab({}, {}, {}, {})",
+            self.l, self.a, self.b, self.alpha
+        )
+    }
+}
+
+impl fmt::Display for Oklch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Oklch({}, {}, {}, {})",
+            self.l, self.c, self.h, self.alpha
+        )
+    }
+}
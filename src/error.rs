@@ -7,16 +7,67 @@ pub enum ErrorKind {
     InvalidRgb,
     /// Error when the provided HSL color format is invalid.
     InvalidHsl,
+    /// Error when the provided HWB color format is invalid.
+    InvalidHwb,
+    /// Error when the provided Lab color format is invalid.
+    InvalidLab,
+    /// Error when the provided LCH color format is invalid.
+    InvalidLch,
+    /// Error when the provided OKLab color format is invalid.
+    InvalidOklab,
+    /// Error when the provided OKLCH color format is invalid.
+    InvalidOklch,
+    /// Error when the provided `color()` format is invalid.
+    InvalidColor,
+    /// Error when the provided color space name is invalid.
+    InvalidColorspace,
+    /// Error when the provided hue interpolation method is invalid.
+    InvalidHueInterpolation,
+    /// Error when the provided `color-mix()` format is invalid.
+    InvalidColorMix,
+    /// Error when the provided `light-dark()` format is invalid.
+    InvalidLightDark,
+    /// Error when the provided system color is invalid or cannot be resolved.
+    InvalidSystemColor,
+    /// Error when the provided math expression (e.g. `calc()`) is invalid.
+    InvalidCalc,
+    /// Error when a math expression combines values of incompatible types (e.g. `10deg + 10%`).
+    IncompatibleUnits,
+    /// Error when a `var()` has neither a value nor a fallback, is part of a cycle, or when
+    /// `currentColor` is used without a current color.
+    UnresolvedVariable,
     /// Error when the provided color function is invalid.
     InvalidFunction,
     /// error when the provided gradient format is invalid.
     InvalidGradient,
     /// error when the provided gradient coordinates is invalid.
     InvalidGradientCoordinates,
+    /// Error when a pixel buffer does not match the size of the image to render.
+    InvalidBufferSize,
     /// Error when the provided darken format is invalid.
     InvalidDarken,
     /// Error when the provided lighten format is invalid.
     InvalidLighten,
+    /// Error when the provided saturate format is invalid.
+    InvalidSaturate,
+    /// Error when the provided desaturate format is invalid.
+    InvalidDesaturate,
+    /// Error when the provided adjust-hue (or spin) format is invalid.
+    InvalidAdjustHue,
+    /// Error when the provided grayscale format is invalid.
+    InvalidGrayscale,
+    /// Error when the provided complement format is invalid.
+    InvalidComplement,
+    /// Error when the provided invert format is invalid.
+    InvalidInvert,
+    /// Error when the provided fade (fadein or fadeout) format is invalid.
+    InvalidFade,
+    /// Error when the provided tint format is invalid.
+    InvalidTint,
+    /// Error when the provided shade format is invalid.
+    InvalidShade,
+    /// Error when the provided mix format is invalid.
+    InvalidMix,
     #[cfg(any(feature = "theme", feature = "theme_yml"))]
     /// Error when the provided theme color is invalid.
     InvalidThemeColor,
@@ -36,10 +87,35 @@ impl core::fmt::Display for ErrorKind {
             Self::InvalidHex => write!(f, "invalid hex format"),
             Self::InvalidRgb => write!(f, "invalid rgb format"),
             Self::InvalidHsl => write!(f, "invalid hsl format"),
+            Self::InvalidHwb => write!(f, "invalid hwb format"),
+            Self::InvalidLab => write!(f, "invalid lab format"),
+            Self::InvalidLch => write!(f, "invalid lch format"),
+            Self::InvalidOklab => write!(f, "invalid oklab format"),
+            Self::InvalidOklch => write!(f, "invalid oklch format"),
+            Self::InvalidColor => write!(f, "invalid color format"),
+            Self::InvalidColorspace => write!(f, "invalid color space"),
+            Self::InvalidHueInterpolation => write!(f, "invalid hue interpolation method"),
+            Self::InvalidColorMix => write!(f, "invalid color-mix format"),
+            Self::InvalidLightDark => write!(f, "invalid light-dark format"),
+            Self::InvalidSystemColor => write!(f, "invalid system color"),
+            Self::InvalidCalc => write!(f, "invalid math expression"),
+            Self::IncompatibleUnits => write!(f, "incompatible units in math expression"),
+            Self::UnresolvedVariable => write!(f, "unresolved variable"),
             Self::InvalidGradient => write!(f, "invalid gradient format"),
             Self::InvalidGradientCoordinates => write!(f, "invalid gradient coordinates format"),
+            Self::InvalidBufferSize => write!(f, "invalid pixel buffer size"),
             Self::InvalidDarken => write!(f, "invalid darken format"),
             Self::InvalidLighten => write!(f, "invalid lighten format"),
+            Self::InvalidSaturate => write!(f, "invalid saturate format"),
+            Self::InvalidDesaturate => write!(f, "invalid desaturate format"),
+            Self::InvalidAdjustHue => write!(f, "invalid adjust-hue format"),
+            Self::InvalidGrayscale => write!(f, "invalid grayscale format"),
+            Self::InvalidComplement => write!(f, "invalid complement format"),
+            Self::InvalidInvert => write!(f, "invalid invert format"),
+            Self::InvalidFade => write!(f, "invalid fade format"),
+            Self::InvalidTint => write!(f, "invalid tint format"),
+            Self::InvalidShade => write!(f, "invalid shade format"),
+            Self::InvalidMix => write!(f, "invalid mix format"),
             Self::InvalidFunction => write!(f, "invalid color function"),
             Self::InvalidUnknown => write!(f, "invalid unknown format"),
             #[cfg(feature = "theme")]
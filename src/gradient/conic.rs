@@ -0,0 +1,46 @@
+// The geometry of `conic-gradient()`, e.g. `from 90deg at 50% 50%`.
+use core::fmt;
+
+use super::LengthPercentage;
+
+/// The geometry of a conic gradient: color stops go clockwise around `center`, from `angle`
+/// (0.0) to a full turn later (1.0).
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schema_jsonrs::JsonSchema))]
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct ConicGradient {
+    /// The angle of the start of the gradient in degrees, clockwise from the top.
+    pub angle: f32,
+    /// The [x, y] position of the center, from the top left corner of the box.
+    pub center: [LengthPercentage; 2],
+}
+
+impl Default for ConicGradient {
+    /// `from 0deg at center`.
+    fn default() -> Self {
+        Self {
+            angle: 0.0,
+            center: [LengthPercentage::Percentage(0.5); 2],
+        }
+    }
+}
+
+impl ConicGradient {
+    /// The [x, y] center in pixels, for a box of the given size.
+    pub fn center(&self, width: f32, height: f32) -> [f32; 2] {
+        [
+            self.center[0].resolve(width),
+            self.center[1].resolve(height),
+        ]
+    }
+}
+
+impl fmt::Display for ConicGradient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "from {}deg at {} {}",
+            self.angle, self.center[0], self.center[1]
+        )
+    }
+}
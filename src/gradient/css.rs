@@ -0,0 +1,482 @@
+// Serialization of gradients back into CSS, e.g. `linear-gradient(45deg, #ff0000 10%, #0000ff)`.
+use crate::parser::fixup_positions;
+use crate::{Colorspace, HueInterpolation};
+
+use super::{
+    Easing, Gradient, GradientCoordinates, GradientKind, GradientStop, LengthPercentage,
+    LinearDirection, RadialGradient, RadialShape, RadialSize, parse_coordinates,
+};
+
+impl Gradient {
+    /// The gradient as a CSS string, which parses back to the same gradient.
+    ///
+    /// Evenly spaced `gradient()` gradients keep that syntax, other gradients are written as
+    /// `linear-gradient()`, `radial-gradient()` or `conic-gradient()` (or their `repeating-`
+    /// versions), leaving out the default direction, shape and center. Colors are written with
+    /// [`Solid::to_css_string`](crate::Solid::to_css_string).
+    ///
+    /// Linear gradients without a CSS direction that `gradient()` cannot express either (e.g.
+    /// created from custom [`GradientCoordinates`]) are written with the same angle, and their
+    /// stops moved to the same points of a square box.
+    ///
+    /// CSS gradient functions need at least two color stops, so a gradient with a single stop
+    /// is written as `gradient()` when it can express it, and with the stop twice otherwise. A
+    /// gradient without stops is written as `gradient()` with just its direction.
+    ///
+    /// ```
+    /// use colorparser_css::Gradient;
+    ///
+    /// let gradient = Gradient::try_from("linear-gradient(45deg, red 10%, 30%, blue)").unwrap();
+    /// let css = gradient.to_css_string();
+    ///
+    /// assert_eq!(css, "linear-gradient(45deg, #ff0000 10%, 30%, #0000ff)");
+    /// assert_eq!(Gradient::try_from(css.as_str()).unwrap(), gradient);
+    /// ```
+    pub fn to_css_string(&self) -> String {
+        if self.stops.len() < 2 {
+            if let GradientKind::Linear(coordinates) = &self.kind
+                && let Some(css) = self.to_legacy_css_string(coordinates)
+            {
+                return css;
+            }
+            if self.stops.is_empty() {
+                return "gradient(to right)".to_string();
+            }
+        }
+
+        let (function, mut prelude, mut stops) = match &self.kind {
+            GradientKind::Linear(coordinates) => match coordinates.direction {
+                Some(direction) => ("linear", linear_direction(direction), self.stops.clone()),
+                None => {
+                    if let Some(css) = self.to_legacy_css_string(coordinates) {
+                        return css;
+                    }
+                    let (angle, stops) = self.to_css_angle(coordinates);
+                    (
+                        "linear",
+                        linear_direction(LinearDirection::Angle(angle)),
+                        stops,
+                    )
+                }
+            },
+            GradientKind::Radial(radial) => ("radial", radial_shape(radial), self.stops.clone()),
+            GradientKind::Conic(conic) => {
+                let mut prelude = Vec::new();
+                if conic.angle != 0.0 {
+                    prelude.push(format!("from {}deg", conic.angle));
+                }
+                if conic.center != [LengthPercentage::Percentage(0.5); 2] {
+                    prelude.push(format!("at {}", center(&conic.center)));
+                }
+                ("conic", prelude, self.stops.clone())
+            }
+        };
+
+        match (self.interpolation_space, self.hue_interpolation) {
+            (Colorspace::Srgb, _) => {}
+            (space, HueInterpolation::Shorter) => prelude.push(format!("in {space}")),
+            (space, hue) => prelude.push(format!("in {space} {hue} hue")),
+        }
+
+        if let [stop] = &stops[..] {
+            stops.push(stop.clone());
+        }
+
+        let mut args = Vec::with_capacity(stops.len() * 2);
+        if !prelude.is_empty() {
+            args.push(prelude.join(" "));
+        }
+        let conic = matches!(self.kind, GradientKind::Conic(_));
+        args.extend(css_stop_list(&stops, conic));
+
+        let repeating = if self.repeating { "repeating-" } else { "" };
+        format!("{repeating}{function}-gradient({})", args.join(", "))
+    }
+
+    /// `gradient(<color>, ..., <angle>)`, for evenly spaced colors in a direction that parses
+    /// back to the same coordinates. The `to <side-or-corner>` directions of `gradient()` are
+    /// the CSS ones, so they are written as `linear-gradient()`, unless there are fewer than
+    /// two colors.
+    fn to_legacy_css_string(&self, coordinates: &GradientCoordinates) -> Option<String> {
+        let step = 1.0 / self.stops.len().saturating_sub(1).max(1) as f32;
+        let is_legacy = !self.repeating
+            && self.interpolation_space == Colorspace::Srgb
+            && self.stops.iter().enumerate().all(|(i, stop)| {
+                stop.position == i as f32 * step
+                    && stop.easing == Easing::Linear
+                    && stop.color.to_css_string().starts_with('#')
+            });
+        if !is_legacy {
+            return None;
+        }
+
+        let direction = match coordinates.direction {
+            Some(LinearDirection::Angle(180.0)) if self.stops.len() < 2 => "to bottom".to_string(),
+            Some(direction) if self.stops.len() < 2 => linear_direction(direction)
+                .pop()
+                .filter(|direction| direction.starts_with("to "))?,
+            Some(_) => return None,
+            None => {
+                // `gradient()` angles start from the right, counterclockwise.
+                let [dx, dy] = [
+                    coordinates.end[0] - coordinates.start[0],
+                    coordinates.end[1] - coordinates.start[1],
+                ];
+                let angle = format!("{}deg", (-dy).atan2(dx).to_degrees());
+                if parse_coordinates(&angle).ok().as_ref() != Some(coordinates) {
+                    return None;
+                }
+                angle
+            }
+        };
+
+        let args = self
+            .colors()
+            .map(|color| color.to_css_string())
+            .chain([direction])
+            .collect::<Vec<_>>();
+
+        Some(format!("gradient({})", args.join(", ")))
+    }
+
+    /// The CSS angle of the line from `start` to `end`, and the stops at the positions of the
+    /// same points along the line of that angle in a square box.
+    fn to_css_angle(&self, coordinates: &GradientCoordinates) -> (f32, Vec<GradientStop>) {
+        let [dx, dy] = [
+            coordinates.end[0] - coordinates.start[0],
+            coordinates.end[1] - coordinates.start[1],
+        ];
+        let angle = dx.atan2(-dy).to_degrees().rem_euclid(360.0);
+
+        let css =
+            GradientCoordinates::from_css_direction(&format!("{angle}deg")).expect("a valid angle");
+        let line = [css.end[0] - css.start[0], css.end[1] - css.start[1]];
+        let length = line[0] * line[0] + line[1] * line[1];
+        let project = |[x, y]: [f32; 2]| {
+            ((x - css.start[0]) * line[0] + (y - css.start[1]) * line[1]) / length
+        };
+
+        let offset = project(coordinates.start);
+        let scale = project(coordinates.end) - offset;
+
+        let stops = self
+            .stops
+            .iter()
+            .map(|stop| GradientStop {
+                position: offset + stop.position * scale,
+                ..stop.clone()
+            })
+            .collect();
+
+        (angle, stops)
+    }
+}
+
+/// The `<angle>` or `to <side-or-corner>` of a linear gradient, empty for `to bottom`.
+fn linear_direction(direction: LinearDirection) -> Vec<String> {
+    let direction = match direction {
+        LinearDirection::Angle(180.0) => return Vec::new(),
+        LinearDirection::Angle(0.0) => "to top".to_string(),
+        LinearDirection::Angle(90.0) => "to right".to_string(),
+        LinearDirection::Angle(270.0) => "to left".to_string(),
+        LinearDirection::Angle(angle) => format!("{angle}deg"),
+        LinearDirection::Corner([x, y]) => {
+            let y = if y == 0.0 { "top" } else { "bottom" };
+            let x = if x == 0.0 { "left" } else { "right" };
+            format!("to {y} {x}")
+        }
+    };
+
+    vec![direction]
+}
+
+/// The `[<shape> || <size>] [at <position>]` of a radial gradient, without the defaults.
+fn radial_shape(radial: &RadialGradient) -> Vec<String> {
+    let default = RadialGradient::default();
+    let mut prelude = Vec::new();
+
+    if radial.shape != default.shape {
+        prelude.push(radial.shape.to_string());
+    }
+    match radial.size {
+        RadialSize::FarthestCorner => {}
+        RadialSize::Explicit(rx, ry) if radial.shape == RadialShape::Circle && rx == ry => {
+            prelude.push(length_percentage(rx))
+        }
+        RadialSize::Explicit(rx, ry) => prelude.push(format!(
+            "{} {}",
+            length_percentage(rx),
+            length_percentage(ry)
+        )),
+        size => prelude.push(size.to_string()),
+    }
+    if radial.center != default.center {
+        prelude.push(format!("at {}", center(&radial.center)));
+    }
+
+    prelude
+}
+
+fn center([x, y]: &[LengthPercentage; 2]) -> String {
+    format!("{} {}", length_percentage(*x), length_percentage(*y))
+}
+
+fn length_percentage(length: LengthPercentage) -> String {
+    match length {
+        LengthPercentage::Length(px) => format!("{px}px"),
+        LengthPercentage::Percentage(t) => percentage(t),
+    }
+}
+
+/// The color stops and the color hints or easings between them.
+///
+/// Positions that the parser resolves to the same values are left out, e.g. the ones of evenly
+/// spaced stops, which may not be written exactly as percentages.
+fn css_stop_list(stops: &[GradientStop], conic: bool) -> Vec<String> {
+    let resolved = stops.iter().map(|stop| stop.position).collect::<Vec<_>>();
+    let mut positions = resolved.iter().copied().map(Some).collect::<Vec<_>>();
+    for i in 0..positions.len() {
+        let mut omitted = positions.clone();
+        omitted[i] = None;
+        if fixup_positions(&omitted) == resolved {
+            positions = omitted;
+        }
+    }
+
+    let mut list = Vec::with_capacity(stops.len() * 2);
+
+    for (i, stop) in stops.iter().enumerate() {
+        let color = stop.color.to_css_string();
+        list.push(match positions[i] {
+            Some(position) => format!("{color} {}", stop_position(position, conic)),
+            None => color,
+        });
+
+        let Some(next) = stops.get(i + 1) else {
+            break;
+        };
+        match stop.easing {
+            Easing::Linear => {}
+            Easing::Hint(hint) => {
+                // The position in the gradient that gives back the same hint once parsed.
+                let (from, span) = (stop.position, next.position - stop.position);
+                let guess = (from as f64 + span as f64 * hint as f64) * 100.0;
+                let position = exact(hint, guess, |p| {
+                    (((p / 100.0).max(from) - from) / span).min(1.0)
+                })
+                .unwrap_or(guess as f32);
+                list.push(format!("{position}%"));
+            }
+            easing => list.push(easing.to_string()),
+        }
+    }
+
+    list
+}
+
+/// A stop position as a percentage, or as an angle when only an angle parses back to the same
+/// position of a conic gradient.
+fn stop_position(t: f32, conic: bool) -> String {
+    match exact(t, t as f64 * 100.0, |p| p / 100.0) {
+        Some(p) => format!("{p}%"),
+        None if conic => match exact(t, t as f64 * 360.0, |deg| deg / 360.0) {
+            Some(deg) => format!("{deg}deg"),
+            None => percentage(t),
+        },
+        None => percentage(t),
+    }
+}
+
+/// `t` as a percentage, which parses back to the same `f32` when possible.
+fn percentage(t: f32) -> String {
+    let guess = t as f64 * 100.0;
+    format!(
+        "{}%",
+        exact(t, guess, |p| p / 100.0).unwrap_or(guess as f32)
+    )
+}
+
+/// The shortest number around `guess` that `parse` turns back into `value`, since e.g. the
+/// percentage `30.000002%` of `0.3` would be parsed into `0.30000002`. Not every `f32` can be
+/// written this way.
+fn exact(value: f32, guess: f64, parse: impl Fn(f32) -> f32) -> Option<f32> {
+    let n = guess as f32;
+
+    [n, n.next_down(), n.next_up()]
+        .into_iter()
+        .filter(|&n| parse(n) == value)
+        .min_by_key(|n| n.to_string().len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solid;
+
+    #[test]
+    fn test_to_css_string() {
+        let cases = [
+            (
+                "gradient(#ff0000, #00ff00, #0000ff, 45deg)",
+                "gradient(#ff0000, #00ff00, #0000ff, 45deg)",
+            ),
+            (
+                "gradient(#ff0000, #0000ff, to right)",
+                "linear-gradient(to right, #ff0000, #0000ff)",
+            ),
+            (
+                "linear-gradient(45deg, red 10%, blue 40% 60%, green)",
+                "linear-gradient(45deg, #ff0000 10%, #0000ff 40%, #0000ff 60%, #008000)",
+            ),
+            (
+                "linear-gradient(to bottom, red 0%, blue, lime 100%)",
+                "linear-gradient(#ff0000, #0000ff, #00ff00)",
+            ),
+            (
+                "linear-gradient(to left top in oklch longer hue, red, blue)",
+                "linear-gradient(to top left in oklch longer hue, #ff0000, #0000ff)",
+            ),
+            (
+                "linear-gradient(red, 25%, blue, ease-in-out, lime, steps(4, start), black)",
+                "linear-gradient(#ff0000, 25%, #0000ff, ease-in-out, #00ff00, \
+                 steps(4, jump-start), #000000)",
+            ),
+            (
+                "radial-gradient(closest-side, red, blue)",
+                "radial-gradient(closest-side, #ff0000, #0000ff)",
+            ),
+            (
+                "repeating-radial-gradient(circle 10px at 30% 40%, red, blue 20%)",
+                "repeating-radial-gradient(circle 10px at 30% 40%, #ff0000, #0000ff 20%)",
+            ),
+            (
+                "conic-gradient(from 90deg at 25% 75% in hsl, red, blue 0.25turn, red)",
+                "conic-gradient(from 90deg at 25% 75% in hsl, #ff0000, #0000ff 25%, #ff0000)",
+            ),
+            (
+                "linear-gradient(in hsl, hsl(none 0% 50%), hsl(240 100% 50%))",
+                "linear-gradient(in hsl, hsl(none 0% 50%), #0000ff)",
+            ),
+            (
+                "linear-gradient(color(srgb none 0.3 1.2 / 0.25), #ffffff66)",
+                "linear-gradient(color(srgb none 0.3 1.2 / 0.25), #ffffff66)",
+            ),
+        ];
+
+        for (s, css) in cases {
+            let gradient = Gradient::try_from(s).unwrap();
+            assert_eq!(gradient.to_css_string(), css, "{s}");
+            assert_eq!(gradient.to_string(), css, "{s}");
+            assert_eq!(Gradient::try_from(css).unwrap(), gradient, "{s}");
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let cases = [
+            "linear-gradient(33.3deg, rgb(10.5 20 30), 12.345%, hsl(120 50% 50% / 30%) 1.5% 77.7%)",
+            "linear-gradient(in oklab, oklch(70% 0.1 250), lab(50 20 -30), 0.1%, #89b4fa)",
+            "radial-gradient(20px 35% at 10px 2.5%, red, blue 10%, 11%, green)",
+            "repeating-conic-gradient(from -45.5deg, red, 0.1turn, blue 0.3333turn)",
+            "linear-gradient(red, cubic-bezier(0.1, -0.7, 0.9, 1.3), blue, steps(3), green)",
+            "linear-gradient(in hsl, hsl(none 100% 50%), hsl(none 0% 50% / none), blue)",
+            "conic-gradient(in oklch, oklch(0.7 0.1 none), lab(60 none none) 40%, red)",
+        ];
+
+        for s in cases {
+            let gradient = Gradient::try_from(s).unwrap();
+            let css = gradient.to_css_string();
+            let parsed = Gradient::try_from(css.as_str()).unwrap();
+            assert_eq!(parsed, gradient, "{s} -> {css}");
+            assert_eq!(parsed.to_css_string(), css, "{s}");
+        }
+
+        // Gradients with fewer than two stops are written as `gradient()`.
+        let cases = [
+            ("gradient(#fff)", "gradient(#ffffff, to right)"),
+            ("gradient(#fff, to right)", "gradient(#ffffff, to right)"),
+            (
+                "gradient(#fff, to top left)",
+                "gradient(#ffffff, to top left)",
+            ),
+            ("gradient(#fff, 45deg)", "gradient(#ffffff, 45deg)"),
+            ("gradient(to bottom)", "gradient(to bottom)"),
+            ("gradient()", "gradient(to right)"),
+        ];
+
+        for (s, expected) in cases {
+            let gradient = Gradient::try_from(s).unwrap();
+            let css = gradient.to_css_string();
+            assert_eq!(css, expected, "{s}");
+            assert_eq!(Gradient::try_from(css.as_str()).unwrap(), gradient, "{s}");
+        }
+
+        let radial = Gradient {
+            stops: vec![GradientStop::new(Solid::new(1.0, 0.0, 0.0, 1.0), 0.0)],
+            ..Gradient::try_from("radial-gradient(circle, red, blue)").unwrap()
+        };
+        let css = radial.to_css_string();
+        assert_eq!(css, "radial-gradient(circle, #ff0000, #ff0000 0%)");
+        assert!(Gradient::try_from(css.as_str()).is_ok());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize() {
+        use serde::Deserialize;
+        use serde::de::IntoDeserializer;
+        use serde::de::value::{Error, StrDeserializer};
+
+        use crate::{Color, ColorValue};
+
+        fn de(s: &str) -> StrDeserializer<'_, Error> {
+            s.into_deserializer()
+        }
+
+        let s = "repeating-linear-gradient(to top right in oklch, #89b4fa, 10%, #f38ba8 20%)";
+        let gradient = Gradient::deserialize(de(s)).unwrap();
+        assert_eq!(gradient, Gradient::try_from(s).unwrap());
+
+        let color = Color::deserialize(de(s)).unwrap();
+        assert_eq!(color.to_css_string(), gradient.to_css_string());
+        assert_eq!(
+            Color::deserialize(de(&color.to_css_string())).unwrap(),
+            color
+        );
+
+        let solid = Solid::deserialize(de("hsl(none 0% 50%)")).unwrap();
+        assert_eq!(
+            Solid::deserialize(de(&solid.to_css_string())).unwrap(),
+            solid
+        );
+
+        let solid = ColorValue::deserialize(de("rgb(10.5 20 30 / 40%)")).unwrap();
+        assert_eq!(
+            ColorValue::deserialize(de(&solid.to_css_string())).unwrap(),
+            solid
+        );
+
+        assert!(Gradient::deserialize(de("#89b4fa")).is_err());
+        assert!(Color::deserialize(de("linear-gradient(red)")).is_err());
+    }
+
+    #[test]
+    fn test_to_css_angle() {
+        let gradient = Gradient::new(
+            GradientKind::Linear(GradientCoordinates::new([0.0, 0.0], [1.0, 1.0])),
+            vec![
+                GradientStop::new(Solid::new(1.0, 0.0, 0.0, 1.0), 0.0),
+                GradientStop::new(Solid::new(0.0, 0.0, 1.0, 1.0), 0.5),
+            ],
+        );
+
+        let css = gradient.to_css_string();
+        assert!(css.starts_with("linear-gradient(135deg, "), "{css}");
+
+        let parsed = Gradient::try_from(css.as_str()).unwrap();
+        for (a, b) in parsed.stops.iter().zip(&gradient.stops) {
+            assert_eq!(a.color, b.color);
+            assert!((a.position - b.position).abs() < 1e-5, "{css}");
+        }
+    }
+}
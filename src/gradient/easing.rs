@@ -0,0 +1,184 @@
+// The transition between two color stops, e.g. a `30%` color hint or `ease-in-out`.
+use core::fmt;
+
+/// How the color changes between a color stop and the next one.
+///
+/// The easing maps the progress between the two stops (0.0 at the first stop, 1.0 at the next
+/// one) to the progress of the interpolation between their colors.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schema_jsonrs::JsonSchema))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub enum Easing {
+    /// The colors are interpolated linearly (the default).
+    #[default]
+    Linear,
+    /// A CSS color hint: the position between the two stops where the color is halfway, where
+    /// 0.0 is the first stop and 1.0 the next one.
+    Hint(f32),
+    /// `cubic-bezier(x1, y1, x2, y2)`, also `ease`, `ease-in`, `ease-out` and `ease-in-out`.
+    CubicBezier(f32, f32, f32, f32),
+    /// `steps(<n>, <step-position>)`, also `step-start` and `step-end`.
+    Steps(u32, StepPosition),
+}
+
+impl Easing {
+    /// `ease`.
+    pub const EASE: Self = Self::CubicBezier(0.25, 0.1, 0.25, 1.0);
+    /// `ease-in`.
+    pub const EASE_IN: Self = Self::CubicBezier(0.42, 0.0, 1.0, 1.0);
+    /// `ease-out`.
+    pub const EASE_OUT: Self = Self::CubicBezier(0.0, 0.0, 0.58, 1.0);
+    /// `ease-in-out`.
+    pub const EASE_IN_OUT: Self = Self::CubicBezier(0.42, 0.0, 0.58, 1.0);
+
+    /// The interpolation progress at the progress `t` between two stops, both in [0..1].
+    pub fn apply(&self, t: f32) -> f32 {
+        match *self {
+            Self::Linear => t,
+            Self::Hint(hint) => hint_progress(hint, t),
+            Self::CubicBezier(x1, y1, x2, y2) => cubic_bezier(x1, y1, x2, y2, t),
+            Self::Steps(steps, position) => position.apply(steps, t),
+        }
+    }
+}
+
+impl fmt::Display for Easing {
+    /// The CSS easing function. Color hints are printed as a percentage of their segment.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Linear => write!(f, "linear"),
+            Self::Hint(hint) => write!(f, "{}%", hint * 100.0),
+            easing if *easing == Self::EASE => write!(f, "ease"),
+            easing if *easing == Self::EASE_IN => write!(f, "ease-in"),
+            easing if *easing == Self::EASE_OUT => write!(f, "ease-out"),
+            easing if *easing == Self::EASE_IN_OUT => write!(f, "ease-in-out"),
+            Self::CubicBezier(x1, y1, x2, y2) => {
+                write!(f, "cubic-bezier({x1}, {y1}, {x2}, {y2})")
+            }
+            Self::Steps(steps, StepPosition::JumpEnd) => write!(f, "steps({steps})"),
+            Self::Steps(steps, position) => write!(f, "steps({steps}, {position})"),
+        }
+    }
+}
+
+/// Where the jumps of `steps()` happen.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schema_jsonrs::JsonSchema))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+pub enum StepPosition {
+    /// `jump-start` (or `start`): the first jump happens at the first stop.
+    JumpStart,
+    /// `jump-end` (or `end`): the last jump happens at the next stop (the default).
+    #[default]
+    JumpEnd,
+    /// `jump-none`: there are no jumps at the stops.
+    JumpNone,
+    /// `jump-both`: there are jumps at both stops.
+    JumpBoth,
+}
+
+impl StepPosition {
+    fn apply(&self, steps: u32, t: f32) -> f32 {
+        let steps = steps as f32;
+        let mut step = (t * steps).floor();
+        if matches!(self, Self::JumpStart | Self::JumpBoth) {
+            step += 1.0;
+        }
+
+        let jumps = match self {
+            Self::JumpStart | Self::JumpEnd => steps,
+            Self::JumpNone => steps - 1.0,
+            Self::JumpBoth => steps + 1.0,
+        };
+        if jumps <= 0.0 {
+            return 0.0;
+        }
+
+        (step.min(jumps) / jumps).clamp(0.0, 1.0)
+    }
+}
+
+impl fmt::Display for StepPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::JumpStart => write!(f, "jump-start"),
+            Self::JumpEnd => write!(f, "jump-end"),
+            Self::JumpNone => write!(f, "jump-none"),
+            Self::JumpBoth => write!(f, "jump-both"),
+        }
+    }
+}
+
+/// The interpolation progress of a CSS color hint, which is 50% at the hint.
+fn hint_progress(hint: f32, t: f32) -> f32 {
+    if hint <= 0.0 {
+        return 1.0;
+    }
+    if hint >= 1.0 {
+        return if t >= 1.0 { 1.0 } else { 0.0 };
+    }
+
+    t.powf(0.5f32.ln() / hint.ln())
+}
+
+/// The `y` of the curve from (0, 0) to (1, 1) with the control points (x1, y1) and (x2, y2), at
+/// the `x` of `t`.
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, t: f32) -> f32 {
+    let bezier = |p1: f32, p2: f32, s: f32| {
+        let r = 1.0 - s;
+        3.0 * r * r * s * p1 + 3.0 * r * s * s * p2 + s * s * s
+    };
+
+    // `x` increases with `s` as both x1 and x2 are in [0..1], so a bisection converges.
+    let (mut low, mut high) = (0.0f32, 1.0f32);
+    let mut s = t;
+    for _ in 0..32 {
+        let x = bezier(x1, x2, s);
+        if (x - t).abs() < 1e-6 {
+            break;
+        }
+        if x < t {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) / 2.0;
+    }
+
+    bezier(y1, y2, s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_easing() {
+        let cases = [
+            (Easing::Linear, 0.25, 0.25),
+            (Easing::Hint(0.5), 0.25, 0.25),
+            (Easing::Hint(0.25), 0.25, 0.5),
+            (Easing::Hint(0.0), 0.25, 1.0),
+            (Easing::EASE_IN_OUT, 0.5, 0.5),
+            (Easing::CubicBezier(0.0, 0.0, 1.0, 1.0), 0.3, 0.3),
+            (Easing::Steps(4, StepPosition::JumpEnd), 0.3, 0.25),
+            (Easing::Steps(4, StepPosition::JumpEnd), 0.99, 0.75),
+            (Easing::Steps(4, StepPosition::JumpStart), 0.0, 0.25),
+            (Easing::Steps(4, StepPosition::JumpStart), 0.8, 1.0),
+            (Easing::Steps(3, StepPosition::JumpNone), 0.5, 0.5),
+            (Easing::Steps(3, StepPosition::JumpBoth), 0.5, 0.5),
+            (Easing::Steps(1, StepPosition::JumpNone), 0.5, 0.0),
+        ];
+
+        for (easing, t, expected) in cases {
+            assert!(
+                (easing.apply(t) - expected).abs() < 1e-5,
+                "{easing} at {t}: {}",
+                easing.apply(t)
+            );
+        }
+
+        assert!(Easing::EASE_IN.apply(0.25) < 0.25);
+        assert!(Easing::EASE_OUT.apply(0.25) > 0.25);
+    }
+}
@@ -0,0 +1,835 @@
+use core::fmt;
+use std::f32::consts::PI;
+
+#[cfg(feature = "schema")]
+use schema_jsonrs::{JsonSchema, Schema, SchemaGenerator, json_schema};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Visitor};
+#[cfg(feature = "schema")]
+use std::borrow::Cow;
+
+use crate::{
+    Colorspace, Error, ErrorKind, HueInterpolation, Result, Solid, parser::parse_gradient,
+};
+
+mod conic;
+mod css;
+mod easing;
+mod radial;
+mod raster;
+
+pub use conic::ConicGradient;
+pub use easing::{Easing, StepPosition};
+pub use radial::{RadialGradient, RadialShape, RadialSize};
+pub use raster::RasterOptions;
+
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+pub struct Gradient {
+    /// The shape of the gradient.
+    pub kind: GradientKind,
+    /// The color stops, with their positions resolved and in increasing order.
+    pub stops: Vec<GradientStop>,
+    /// Whether the stops repeat along the gradient (`repeating-*-gradient()`), with a period of
+    /// the distance between the first and last stops.
+    pub repeating: bool,
+    /// The color space colors are interpolated in between stops (`in <space>`), sRGB by
+    /// default.
+    pub interpolation_space: Colorspace,
+    /// How hues are interpolated in polar color spaces (`<hue-method> hue`).
+    pub hue_interpolation: HueInterpolation,
+}
+
+impl Gradient {
+    /// Create a non-repeating gradient, interpolated in sRGB.
+    pub fn new(kind: GradientKind, stops: Vec<GradientStop>) -> Self {
+        Self {
+            kind,
+            stops,
+            repeating: false,
+            interpolation_space: Colorspace::default(),
+            hue_interpolation: HueInterpolation::default(),
+        }
+    }
+
+    /// Create a linear gradient with the colors evenly spaced from the start to the end of
+    /// `direction`, as the `direction` and `colors` fields of `Gradient` before 0.2.0 did.
+    pub fn linear(direction: GradientCoordinates, colors: Vec<Solid>) -> Self {
+        let step = 1.0 / colors.len().saturating_sub(1).max(1) as f32;
+        let stops = colors
+            .into_iter()
+            .enumerate()
+            .map(|(i, color)| GradientStop::new(color, i as f32 * step))
+            .collect();
+
+        Self::new(GradientKind::Linear(direction), stops)
+    }
+
+    /// The start and end of a linear gradient, `None` for the other kinds of gradients.
+    #[deprecated(
+        since = "0.2.0",
+        note = "match `kind` against `GradientKind::Linear` instead"
+    )]
+    pub fn direction(&self) -> Option<&GradientCoordinates> {
+        match &self.kind {
+            GradientKind::Linear(direction) => Some(direction),
+            _ => None,
+        }
+    }
+
+    /// The color at `t` between the colors of two stops, in the interpolation space of the
+    /// gradient.
+    pub fn interpolate(&self, from: &Solid, to: &Solid, t: f32) -> Solid {
+        from.interpolate(to, t, self.interpolation_space, self.hue_interpolation)
+    }
+
+    /// The colors of the stops, in order.
+    pub fn colors(&self) -> impl Iterator<Item = &Solid> {
+        self.stops.iter().map(|stop| &stop.color)
+    }
+
+    /// The distance between the first and last stops, over which repeating gradients repeat.
+    pub fn period(&self) -> f32 {
+        match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => last.position - first.position,
+            _ => 0.0,
+        }
+    }
+
+    /// The position along the stops that applies at `t`.
+    ///
+    /// For repeating gradients, `t` is wrapped into the range between the first and last
+    /// stops, e.g. `1.3` becomes `0.3` for stops from 0% to 100% and `0.1` becomes `0.3` for
+    /// stops from 20% to 40%. Other gradients return `t` as is.
+    pub fn repeat_position(&self, t: f32) -> f32 {
+        let period = self.period();
+        if !self.repeating || period <= 0.0 {
+            return t;
+        }
+
+        let first = self.stops[0].position;
+        first + (t - first).rem_euclid(period)
+    }
+
+    /// The color at position `t` along the gradient, where 0.0 is the start and 1.0 the end.
+    ///
+    /// Before the first stop and after the last one, the gradient has the color of that stop,
+    /// unless it is repeating. Colors between stops are interpolated in
+    /// [`Gradient::interpolation_space`], following the [`GradientStop::easing`] of the first
+    /// one. A gradient without stops is transparent, and a NaN `t` (or an infinite one in a
+    /// repeating gradient) gets the color of the first stop.
+    ///
+    /// ```
+    /// use colorparser_css::Gradient;
+    ///
+    /// let gradient = Gradient::try_from("linear-gradient(red 20%, blue 80%)").unwrap();
+    ///
+    /// assert_eq!(gradient.at(0.0).to_hex_string(), "#ff0000");
+    /// assert_eq!(gradient.at(0.5).to_hex_string(), "#800080");
+    /// assert_eq!(gradient.at(1.0).to_hex_string(), "#0000ff");
+    /// ```
+    pub fn at(&self, t: f32) -> Solid {
+        let (Some(first), Some(last)) = (self.stops.first(), self.stops.last()) else {
+            return Solid::new(0.0, 0.0, 0.0, 0.0);
+        };
+
+        // A repeating gradient whose stops are all at the same position is rendered as its
+        // average color.
+        if self.repeating && self.period() <= 0.0 {
+            return self.average();
+        }
+
+        // Infinite positions of repeating gradients wrap to NaN, which has no color of its own.
+        let t = self.repeat_position(t);
+        if t.is_nan() || t < first.position {
+            return first.color.clone();
+        }
+        if t >= last.position {
+            return last.color.clone();
+        }
+
+        // The first stop after `t`: when several stops share a position, the last one applies.
+        let i = self.stops.partition_point(|stop| stop.position <= t);
+        let (from, to) = (&self.stops[i - 1], &self.stops[i]);
+        let t = (t - from.position) / (to.position - from.position);
+
+        self.interpolate(&from.color, &to.color, from.easing.apply(t))
+    }
+
+    /// `n` colors evenly spaced along the gradient, from its start to its end.
+    pub fn sample(&self, n: usize) -> Vec<Solid> {
+        self.colors_iter(n).collect()
+    }
+
+    /// Iterates over `n` colors evenly spaced along the gradient, from its start to its end.
+    pub fn colors_iter(&self, n: usize) -> impl Iterator<Item = Solid> + '_ {
+        let step = 1.0 / n.saturating_sub(1).max(1) as f32;
+        (0..n).map(move |i| self.at(i as f32 * step))
+    }
+
+    /// The average color of the stops spread evenly, as the average of the middle colors of
+    /// the segments between them.
+    fn average(&self) -> Solid {
+        if self.stops.len() == 1 {
+            return self.stops[0].color.clone();
+        }
+
+        let middles = self
+            .stops
+            .windows(2)
+            .map(|pair| {
+                let [r, g, b, a] = self
+                    .interpolate(&pair[0].color, &pair[1].color, 0.5)
+                    .to_array();
+                [r * a, g * a, b * a, a]
+            })
+            .collect::<Vec<_>>();
+
+        let n = middles.len() as f32;
+        let [r, g, b, a] = middles.iter().fold([0.0; 4], |sum, c| {
+            [sum[0] + c[0], sum[1] + c[1], sum[2] + c[2], sum[3] + c[3]]
+        });
+
+        if a == 0.0 {
+            return Solid::new(0.0, 0.0, 0.0, 0.0);
+        }
+        Solid::new(r / a, g / a, b / a, a / n)
+    }
+}
+
+impl fmt::Display for Gradient {
+    /// The CSS gradient, see [`Gradient::to_css_string`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_css_string())
+    }
+}
+
+/// The shape of a gradient, along which its color stops are laid out.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schema_jsonrs::JsonSchema))]
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+pub enum GradientKind {
+    /// Stops along the line from `start` to `end`.
+    Linear(GradientCoordinates),
+    /// Stops from the center to the ending shape.
+    Radial(RadialGradient),
+    /// Stops around the center, where 1.0 is a full turn.
+    Conic(ConicGradient),
+}
+
+/// A length in pixels, or a percentage of a reference length (e.g. the width of the box).
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schema_jsonrs::JsonSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum LengthPercentage {
+    /// A length in pixels.
+    Length(f32),
+    /// A fraction of the reference length, where 1.0 is 100%.
+    Percentage(f32),
+}
+
+impl LengthPercentage {
+    /// The length in pixels, against the given reference length.
+    pub fn resolve(&self, reference: f32) -> f32 {
+        match self {
+            Self::Length(px) => *px,
+            Self::Percentage(t) => t * reference,
+        }
+    }
+}
+
+impl fmt::Display for LengthPercentage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Length(px) => write!(f, "{px}px"),
+            Self::Percentage(t) => write!(f, "{}%", t * 100.0),
+        }
+    }
+}
+
+/// A color stop of a gradient.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schema_jsonrs::JsonSchema))]
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct GradientStop {
+    /// The color of the stop.
+    pub color: Solid,
+    /// The position of the stop along the gradient line, where 0.0 is the start and 1.0 the
+    /// end (a full turn for conic gradients). Positions outside [0..1] are allowed.
+    pub position: f32,
+    /// How the color changes from this stop to the next one, e.g. with a color hint.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub easing: Easing,
+}
+
+impl GradientStop {
+    /// Create a color stop at the given position, with a linear transition to the next one.
+    pub fn new(color: Solid, position: f32) -> Self {
+        Self {
+            color,
+            position,
+            easing: Easing::Linear,
+        }
+    }
+}
+
+impl fmt::Display for GradientStop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}%", self.color, self.position * 100.0)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schema_jsonrs::JsonSchema))]
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct GradientCoordinates {
+    /// The [x, y] coordinates for the start point of the gradient.
+    pub start: [f32; 2],
+    /// The [x, y] coordinates for the end point of the gradient.
+    pub end: [f32; 2],
+    /// The CSS direction the coordinates were created from, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    direction: Option<LinearDirection>,
+}
+
+/// The direction of a CSS linear gradient.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schema_jsonrs::JsonSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum LinearDirection {
+    /// An `<angle>` (or `to <side>`) in degrees, clockwise from the top.
+    Angle(f32),
+    /// `to <corner>`, as the [x, y] corner in fractions of the box, e.g. `[1.0, 0.0]` for
+    /// `to top right`. The angle depends on the aspect ratio of the box.
+    Corner([f32; 2]),
+}
+
+/// Implements the `TryFrom` trait to convert a string into a `GradientCoordinates` object.
+/// The string can represent an angle (e.g., "45deg") or a direction (e.g., "to right").
+impl TryFrom<&str> for GradientCoordinates {
+    type Error = Error;
+
+    /// Tries to convert a string into a `GradientCoordinates` struct.
+    ///
+    /// # Parameters
+    /// - `color`: A string representing the gradient direction or angle.
+    ///
+    /// # Returns
+    /// A `Result` that is `Ok(GradientCoordinates)` on success or `Err(WinSolidError)` on failure.
+    fn try_from(color: &str) -> Result<Self> {
+        parse_coordinates(color)
+    }
+}
+
+impl GradientCoordinates {
+    /// Create the coordinates of a line from `start` to `end`, in fractions of the box. They
+    /// have no CSS direction, so they stay the same in every box.
+    pub fn new(start: [f32; 2], end: [f32; 2]) -> Self {
+        Self {
+            start,
+            end,
+            direction: None,
+        }
+    }
+
+    /// The CSS direction the coordinates were created from, if any. `start` and `end` are the
+    /// ones of a square box, see [`GradientCoordinates::for_box`] for other boxes.
+    pub fn direction(&self) -> Option<LinearDirection> {
+        self.direction
+    }
+
+    /// Create the coordinates of a CSS `linear-gradient()` line, from its `<angle>` or
+    /// `to <side-or-corner>` direction. Unlike the `gradient()` directions, angles follow CSS:
+    /// `0deg` points up and angles increase clockwise.
+    ///
+    /// The line goes through the center of the box, and is long enough for the corners to get
+    /// the colors of the first and last stops.
+    pub fn from_css_direction(direction: &str) -> Result<Self> {
+        let words = direction.split_whitespace().collect::<Vec<_>>();
+
+        let css_direction = match words[..] {
+            ["to", side] => side_angle(side).map(LinearDirection::Angle),
+            ["to", a, b] => match (side_corner(a), side_corner(b)) {
+                ([Some(x), None], [None, Some(y)]) | ([None, Some(y)], [Some(x), None]) => {
+                    Some(LinearDirection::Corner([x, y]))
+                }
+                _ => None,
+            },
+            [angle] => parse_angle(angle)
+                .filter(|_| angle == "0" || is_valid_angle(angle))
+                .map(LinearDirection::Angle),
+            _ => None,
+        };
+
+        let css_direction = css_direction
+            .ok_or_else(|| Error::new(ErrorKind::InvalidGradientCoordinates, direction))?;
+
+        Ok(Self::for_direction(css_direction, 1.0, 1.0))
+    }
+
+    /// The coordinates of the same CSS direction in a box of the given size, with `start` and
+    /// `end` still in fractions of the box.
+    ///
+    /// CSS angles are physical, so an `<angle>` in a wide box makes a different line than in a
+    /// square one, and the line is long enough for the corners of that box to get the colors
+    /// of the first and last stops. With `to <corner>`, the line is perpendicular to the
+    /// diagonal between the two other corners, which get the color at 50%.
+    ///
+    /// Coordinates that are not from a CSS direction (e.g. of `gradient()`) are returned as is.
+    ///
+    /// ```
+    /// use colorparser_css::GradientCoordinates;
+    ///
+    /// let coordinates = GradientCoordinates::from_css_direction("90deg").unwrap();
+    /// let wide = GradientCoordinates::from_css_direction("45deg").unwrap().for_box(200.0, 100.0);
+    ///
+    /// assert_eq!(coordinates.for_box(200.0, 100.0), coordinates);
+    /// assert_eq!(wide.start, [0.125, 1.25]);
+    /// assert_eq!(wide.end, [0.875, -0.25]);
+    /// ```
+    pub fn for_box(&self, width: f32, height: f32) -> Self {
+        match self.direction {
+            Some(direction) if width > 0.0 && height > 0.0 => {
+                Self::for_direction(direction, width, height)
+            }
+            _ => self.clone(),
+        }
+    }
+
+    fn for_direction(direction: LinearDirection, width: f32, height: f32) -> Self {
+        let angle = match direction {
+            LinearDirection::Angle(angle) => angle,
+            LinearDirection::Corner([x, y]) => {
+                // Perpendicular to the diagonal between the other corners.
+                let [dx, dy] = [(2.0 * x - 1.0) * height, (2.0 * y - 1.0) * width];
+                dx.atan2(-dy).to_degrees()
+            }
+        };
+
+        // Snap the rounding errors of e.g. `cos(90deg)`, for exact coordinates along the axes.
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let [sin, cos] = [sin, cos].map(|t| if t.abs() < 1e-6 { 0.0 } else { t });
+
+        let half = (width * sin.abs() + height * cos.abs()) / 2.0;
+        let [dx, dy] = [sin * half / width, -cos * half / height];
+
+        Self {
+            start: [0.5 - dx, 0.5 - dy],
+            end: [0.5 + dx, 0.5 + dy],
+            direction: Some(direction),
+        }
+    }
+}
+
+/// The [x, y] corner coordinate of the side of a `to <corner>`.
+fn side_corner(side: &str) -> [Option<f32>; 2] {
+    match side {
+        "top" => [None, Some(0.0)],
+        "right" => [Some(1.0), None],
+        "bottom" => [None, Some(1.0)],
+        "left" => [Some(0.0), None],
+        _ => [None, None],
+    }
+}
+
+/// The CSS angle of `to <side>`.
+fn side_angle(side: &str) -> Option<f32> {
+    match side {
+        "top" => Some(0.0),
+        "right" => Some(90.0),
+        "bottom" => Some(180.0),
+        "left" => Some(270.0),
+        _ => None,
+    }
+}
+
+/// A simple struct representing a line equation (`y = mx + b`).
+#[derive(Debug)]
+struct Line {
+    /// The slope of the line.
+    m: f32,
+    /// The y-intercept of the line
+    b: f32,
+}
+
+impl Line {
+    /// Calculates the y-value for a given x-value using the line equation `y = mx + b`.
+    ///
+    /// # Parameters
+    /// - `x`: The x-value to plug into the equation.
+    ///
+    /// # Returns
+    /// The y-value corresponding to the given x-value.
+    pub fn plug_in_x(&self, x: f32) -> f32 {
+        self.m * x + self.b
+    }
+}
+
+/// Calculates the start and end points of a gradient based on a line equation.
+///
+/// # Parameters
+/// - `line`: A reference to a `Line` struct representing the line equation.
+/// - `x`: The x-value to calculate the corresponding y-value for.
+///
+/// # Returns
+/// A 2-element array `[f32; 2]` representing the calculated x and y coordinates.
+fn calculate_point(line: &Line, x: f32) -> [f32; 2] {
+    match line.plug_in_x(x) {
+        0.0..=1.0 => [x, line.plug_in_x(x)],
+        1.0.. => [(1.0 - line.b) / line.m, 1.0],
+        _ => [-line.b / line.m, 0.0],
+    }
+}
+
+/// Parses a string representation of gradient coordinates, either as an angle or as a direction.
+///
+/// # Parameters
+/// - `coordinates`: A string representing either an angle or a named direction (e.g., "to right").
+///
+/// # Returns
+/// A `Result` that is `Ok(GradientCoordinates)` on success or `Err(WinSolidError)` on failure.
+fn parse_coordinates(coordinates: &str) -> Result<GradientCoordinates> {
+    let angle = parse_angle(coordinates);
+
+    match angle {
+        Some(angle) => {
+            let rad = -angle * PI / 180.0;
+
+            let m = match angle.abs() % 360.0 {
+                90.0 | 270.0 => angle.signum() * f32::MAX,
+                _ => rad.sin() / rad.cos(),
+            };
+
+            let b = -m * 0.5 + 0.5;
+
+            let line = Line { m, b };
+
+            let (x_s, x_e) = match angle.abs() % 360.0 {
+                0.0..90.0 => (0.0, 1.0),
+                90.0..270.0 => (1.0, 0.0),
+                270.0..360.0 => (0.0, 1.0),
+                _ => (0.0, 1.0),
+            };
+
+            let start = calculate_point(&line, x_s);
+            let end = calculate_point(&line, x_e);
+
+            // Adjusting calculations based on the origin being (0.5, 0.5)
+            Ok(GradientCoordinates::new(start, end))
+        }
+        // `to <side-or-corner>` is the same as in CSS.
+        None if is_valid_direction(coordinates) => {
+            GradientCoordinates::from_css_direction(coordinates)
+        }
+        None => Err(Error::new(
+            ErrorKind::InvalidGradientCoordinates,
+            coordinates,
+        )),
+    }
+}
+
+/// Parses a string representing an angle and converts it to radians or degrees.
+///
+/// The angle can be in various units such as "deg", "grad", "rad", or "turn".
+/// The function attempts to parse the angle and convert it into a float value representing the angle in radians.
+///
+/// If no valid angle or unit is found, `None` is returned.
+///
+/// # Parameters
+/// - `s`: A string representing an angle. This string can have a suffix indicating the unit of measurement, such as "deg", "grad", "rad", or "turn".
+///
+/// # Returns
+/// Returns an `Option<f32>`:
+/// - `Some(f32)` if the string is a valid angle with a recognized unit or as a plain number.
+/// - `None` if the string cannot be parsed as a valid angle.
+fn parse_angle(s: &str) -> Option<f32> {
+    s.strip_suffix("deg")
+        .and_then(|s| s.parse().ok())
+        .or_else(|| {
+            s.strip_suffix("grad")
+                .and_then(|s| s.parse().ok())
+                .map(|t: f32| t * 360.0 / 400.0)
+        })
+        .or_else(|| {
+            s.strip_suffix("rad")
+                .and_then(|s| s.parse().ok())
+                .map(|t: f32| t.to_degrees())
+        })
+        .or_else(|| {
+            s.strip_suffix("turn")
+                .and_then(|s| s.parse().ok())
+                .map(|t: f32| t * 360.0)
+        })
+        .or_else(|| s.parse().ok())
+}
+
+pub fn is_valid_direction(direction: &str) -> bool {
+    matches!(
+        direction,
+        "to right"
+            | "to left"
+            | "to top"
+            | "to bottom"
+            | "to top right"
+            | "to top left"
+            | "to bottom right"
+            | "to bottom left"
+    ) || is_valid_angle(direction)
+}
+
+fn is_valid_angle(direction: &str) -> bool {
+    const VALID_SUFFIXES: [&str; 4] = ["deg", "grad", "rad", "turn"];
+
+    VALID_SUFFIXES.iter().any(|&suffix| {
+        direction
+            .strip_suffix(suffix) // Remove the suffix
+            .and_then(|num| num.parse::<f32>().ok()) // Parse the numeric part
+            .is_some()
+    })
+}
+
+/// Implements the `TryFrom` trait to convert a string into a `GradientCoordinates` object.
+/// The string can represent an angle (e.g., "45deg") or a direction (e.g., "to right").
+impl TryFrom<&str> for Gradient {
+    type Error = Error;
+
+    /// Tries to convert a string into a `GradientCoordinates` struct.
+    ///
+    /// # Parameters
+    /// - `color`: A string representing the gradient direction or angle.
+    ///
+    /// # Returns
+    /// A `Result` that is `Ok(GradientCoordinates)` on success or `Err(WinSolidError)` on failure.
+    fn try_from(color: &str) -> Result<Self> {
+        parse_gradient(color, None)
+    }
+}
+
+/// Implement Serde serialization into a CSS gradient string
+#[cfg(feature = "serde")]
+impl Serialize for Gradient {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_css_string())
+    }
+}
+
+/// Implement Serde deserialization from string
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Gradient {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        deserializer.deserialize_str(GradientVisitor)
+    }
+}
+
+#[cfg(feature = "serde")]
+struct GradientVisitor;
+
+#[cfg(feature = "serde")]
+impl Visitor<'_> for GradientVisitor {
+    type Value = Gradient;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a valid css gradient")
+    }
+
+    fn visit_str<E>(self, v: &str) -> core::result::Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Gradient::try_from(v).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Describe the CSS string that Serde (de)serializes as a JSON Schema string
+#[cfg(feature = "schema")]
+impl JsonSchema for Gradient {
+    fn schema_name() -> Cow<'static, str> {
+        "Gradient".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "A CSS gradient, e.g. `linear-gradient(45deg, #ff0000, #0000ff)`",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(gradient: &Gradient, t: f32) -> String {
+        gradient.at(t).to_hex_string()
+    }
+
+    #[test]
+    fn test_for_box() {
+        let assert_box = |direction: &str, start: [f32; 2], end: [f32; 2]| {
+            let coordinates = GradientCoordinates::from_css_direction(direction)
+                .unwrap()
+                .for_box(200.0, 100.0);
+            for (a, b) in coordinates
+                .start
+                .iter()
+                .chain(&coordinates.end)
+                .zip(start.iter().chain(&end))
+            {
+                assert!((a - b).abs() < 1e-5, "{direction}: {coordinates:?}");
+            }
+        };
+
+        assert_box("to top right", [0.3, 1.3], [0.7, -0.3]);
+        assert_box("to left bottom", [0.7, -0.3], [0.3, 1.3]);
+        assert_box("45deg", [0.125, 1.25], [0.875, -0.25]);
+        assert_box("90deg", [0.0, 0.5], [1.0, 0.5]);
+        assert_box("to bottom", [0.5, 0.0], [0.5, 1.0]);
+
+        // Square boxes keep the coordinates.
+        let corner = GradientCoordinates::from_css_direction("to top right").unwrap();
+        let square = corner.for_box(50.0, 50.0);
+        assert!((square.start[0] - corner.start[0]).abs() < 1e-5);
+        assert!((square.end[1] - corner.end[1]).abs() < 1e-5);
+        assert_eq!(corner.for_box(0.0, 50.0), corner);
+
+        let legacy = parse_coordinates("45deg").unwrap();
+        assert_eq!(legacy.for_box(200.0, 100.0), legacy);
+        assert_eq!(legacy.direction(), None);
+
+        let line = GradientCoordinates::new([0.0, 0.0], [1.0, 1.0]);
+        assert_eq!(line.for_box(200.0, 100.0), line);
+        assert_eq!(
+            corner.direction(),
+            Some(LinearDirection::Corner([1.0, 0.0]))
+        );
+    }
+
+    #[test]
+    fn test_at() {
+        let gradient = Gradient::try_from("linear-gradient(red, lime 50%, blue)").unwrap();
+        let cases = [
+            (-1.0, "#ff0000"),
+            (0.0, "#ff0000"),
+            (0.25, "#808000"),
+            (0.5, "#00ff00"),
+            (0.75, "#008080"),
+            (1.0, "#0000ff"),
+            (2.0, "#0000ff"),
+        ];
+        for (t, expected) in cases {
+            assert_eq!(hex(&gradient, t), expected, "{t}");
+        }
+
+        // Hard stops
+        let gradient =
+            Gradient::try_from("linear-gradient(red 50%, blue 50%, lime 50% 100%)").unwrap();
+        assert_eq!(hex(&gradient, 0.49), "#ff0000");
+        assert_eq!(hex(&gradient, 0.5), "#00ff00");
+        assert_eq!(hex(&gradient, 0.75), "#00ff00");
+
+        // Premultiplied alpha
+        let gradient = Gradient::try_from("linear-gradient(transparent, blue)").unwrap();
+        assert_eq!(gradient.at(0.5), Solid::new(0.0, 0.0, 1.0, 0.5));
+
+        let empty = Gradient::new(GradientKind::Radial(RadialGradient::default()), Vec::new());
+        assert_eq!(empty.at(0.5), Solid::new(0.0, 0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_at_interpolation_space() {
+        let srgb = Gradient::try_from("linear-gradient(red, blue)").unwrap();
+        let oklch = Gradient::try_from("linear-gradient(in oklch, red, blue)").unwrap();
+        let longer = Gradient::try_from("linear-gradient(in hsl longer hue, red, blue)").unwrap();
+
+        assert_eq!(hex(&srgb, 0.5), "#800080");
+        assert!(oklch.at(0.5).to_oklch().c > srgb.at(0.5).to_oklch().c);
+        // The longer way from red (0deg) to blue (240deg) goes through green (120deg).
+        assert_eq!(hex(&longer, 0.5), "#00ff00");
+    }
+
+    #[test]
+    fn test_at_repeating() {
+        let gradient =
+            Gradient::try_from("repeating-linear-gradient(red 0%, blue 20%, red 40%)").unwrap();
+        for t in [0.0, 0.4, 0.8, -0.4] {
+            assert_eq!(hex(&gradient, t), "#ff0000", "{t}");
+        }
+        for t in [0.2, 0.6, 1.0] {
+            assert_eq!(hex(&gradient, t), "#0000ff", "{t}");
+        }
+        let (a, b) = (gradient.at(0.5).to_array(), gradient.at(0.1).to_array());
+        assert!(a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-4));
+
+        let solid = Gradient::try_from("repeating-linear-gradient(red 50%, blue 50%)").unwrap();
+        let average = Gradient::try_from("linear-gradient(red, blue)")
+            .unwrap()
+            .at(0.5);
+        assert_eq!(solid.at(0.1), average);
+        assert_eq!(solid.at(0.9), average);
+    }
+
+    #[test]
+    fn test_at_non_finite() {
+        let gradient = Gradient::try_from("linear-gradient(red, blue)").unwrap();
+        assert_eq!(hex(&gradient, f32::NAN), "#ff0000");
+        assert_eq!(hex(&gradient, f32::INFINITY), "#0000ff");
+        assert_eq!(hex(&gradient, f32::NEG_INFINITY), "#ff0000");
+
+        let repeating = Gradient::try_from("repeating-linear-gradient(red, blue 20%)").unwrap();
+        for t in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            assert_eq!(hex(&repeating, t), "#ff0000", "{t}");
+        }
+    }
+
+    #[test]
+    fn test_sample() {
+        let gradient = Gradient::try_from("linear-gradient(black, white)").unwrap();
+
+        let colors = gradient
+            .sample(5)
+            .iter()
+            .map(Solid::to_hex_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            colors,
+            ["#000000", "#404040", "#808080", "#bfbfbf", "#ffffff"]
+        );
+
+        assert!(gradient.sample(0).is_empty());
+        assert_eq!(gradient.sample(1), [gradient.at(0.0)]);
+        assert_eq!(gradient.colors_iter(3).nth(1), Some(gradient.at(0.5)));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_linear() {
+        let direction = GradientCoordinates::try_from("to right").unwrap();
+        let colors = ["#ff0000", "#00ff00", "#0000ff"].map(|s| Solid::from_html(s).unwrap());
+        let gradient = Gradient::linear(direction.clone(), colors.to_vec());
+
+        assert_eq!(
+            gradient,
+            Gradient::try_from("gradient(#ff0000, #00ff00, #0000ff, to right)").unwrap()
+        );
+        assert_eq!(gradient.direction(), Some(&direction));
+        assert!(gradient.colors().eq(&colors));
+
+        let radial = Gradient::try_from("radial-gradient(red, blue)").unwrap();
+        assert_eq!(radial.direction(), None);
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn test_json_schema() {
+        use crate::{Color, ColorValue};
+        use schema_jsonrs::schema_for;
+
+        for schema in [
+            schema_for!(Gradient),
+            schema_for!(Solid),
+            schema_for!(Color),
+            schema_for!(ColorValue),
+        ] {
+            assert_eq!(schema.get("type"), Some(&"string".into()), "{schema:?}");
+        }
+    }
+}
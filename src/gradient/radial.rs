@@ -0,0 +1,189 @@
+// The ending shape of `radial-gradient()`, e.g. `circle closest-side at 30% 40%`.
+use core::fmt;
+
+use super::LengthPercentage;
+
+/// The geometry of a radial gradient: an ellipse (or circle) centered on `center`, whose color
+/// stops go from the center (0.0) to the ending shape (1.0).
+///
+/// Sizes and positions depend on the box the gradient is drawn in, see [`RadialGradient::center`]
+/// and [`RadialGradient::radii`].
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schema_jsonrs::JsonSchema))]
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct RadialGradient {
+    /// Whether the ending shape is a circle or an ellipse.
+    pub shape: RadialShape,
+    /// The size of the ending shape.
+    pub size: RadialSize,
+    /// The [x, y] position of the center, from the top left corner of the box.
+    pub center: [LengthPercentage; 2],
+}
+
+impl Default for RadialGradient {
+    /// `ellipse farthest-corner at center`.
+    fn default() -> Self {
+        Self {
+            shape: RadialShape::Ellipse,
+            size: RadialSize::FarthestCorner,
+            center: [LengthPercentage::Percentage(0.5); 2],
+        }
+    }
+}
+
+impl RadialGradient {
+    /// The [x, y] center in pixels, for a box of the given size.
+    pub fn center(&self, width: f32, height: f32) -> [f32; 2] {
+        [
+            self.center[0].resolve(width),
+            self.center[1].resolve(height),
+        ]
+    }
+
+    /// The horizontal and vertical radii of the ending shape in pixels, for a box of the given
+    /// size. Both radii are the same for circles.
+    pub fn radii(&self, width: f32, height: f32) -> [f32; 2] {
+        let [x, y] = self.center(width, height);
+        let (left, top) = (x.abs(), y.abs());
+        let (right, bottom) = ((width - x).abs(), (height - y).abs());
+        let closest = [left.min(right), top.min(bottom)];
+        let farthest = [left.max(right), top.max(bottom)];
+
+        let circle = self.shape == RadialShape::Circle;
+
+        match self.size {
+            RadialSize::ClosestSide if circle => [closest[0].min(closest[1]); 2],
+            RadialSize::FarthestSide if circle => [farthest[0].max(farthest[1]); 2],
+            RadialSize::ClosestCorner if circle => [closest[0].hypot(closest[1]); 2],
+            RadialSize::FarthestCorner if circle => [farthest[0].hypot(farthest[1]); 2],
+            RadialSize::ClosestSide => closest,
+            RadialSize::FarthestSide => farthest,
+            // The ellipse keeps the aspect ratio of the `-side` one, and goes through the corner.
+            RadialSize::ClosestCorner => closest.map(|r| r * std::f32::consts::SQRT_2),
+            RadialSize::FarthestCorner => farthest.map(|r| r * std::f32::consts::SQRT_2),
+            RadialSize::Explicit(rx, ry) => [rx.resolve(width), ry.resolve(height)],
+        }
+    }
+}
+
+impl fmt::Display for RadialGradient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} at {} {}",
+            self.shape, self.size, self.center[0], self.center[1]
+        )
+    }
+}
+
+/// The ending shape of a radial gradient.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schema_jsonrs::JsonSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+pub enum RadialShape {
+    Circle,
+    Ellipse,
+}
+
+impl fmt::Display for RadialShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Circle => write!(f, "circle"),
+            Self::Ellipse => write!(f, "ellipse"),
+        }
+    }
+}
+
+/// The size of the ending shape of a radial gradient.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schema_jsonrs::JsonSchema))]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum RadialSize {
+    /// The shape touches the side of the box closest to its center.
+    ClosestSide,
+    /// The shape touches the side of the box farthest from its center.
+    FarthestSide,
+    /// The shape goes through the corner of the box closest to its center.
+    ClosestCorner,
+    /// The shape goes through the corner of the box farthest from its center (the default).
+    FarthestCorner,
+    /// Explicit horizontal and vertical radii, the same length for circles.
+    Explicit(LengthPercentage, LengthPercentage),
+}
+
+impl fmt::Display for RadialSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ClosestSide => write!(f, "closest-side"),
+            Self::FarthestSide => write!(f, "farthest-side"),
+            Self::ClosestCorner => write!(f, "closest-corner"),
+            Self::FarthestCorner => write!(f, "farthest-corner"),
+            Self::Explicit(rx, ry) if rx == ry => write!(f, "{rx}"),
+            Self::Explicit(rx, ry) => write!(f, "{rx} {ry}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_radii() {
+        let at = |x, y| [LengthPercentage::Length(x), LengthPercentage::Length(y)];
+        let cases = [
+            (
+                RadialShape::Circle,
+                RadialSize::ClosestSide,
+                at(30.0, 40.0),
+                [30.0, 30.0],
+            ),
+            (
+                RadialShape::Circle,
+                RadialSize::FarthestSide,
+                at(30.0, 40.0),
+                [170.0, 170.0],
+            ),
+            (
+                RadialShape::Circle,
+                RadialSize::ClosestCorner,
+                at(30.0, 40.0),
+                [50.0, 50.0],
+            ),
+            (
+                RadialShape::Ellipse,
+                RadialSize::ClosestSide,
+                at(30.0, 40.0),
+                [30.0, 40.0],
+            ),
+            (
+                RadialShape::Ellipse,
+                RadialSize::FarthestSide,
+                at(100.0, 50.0),
+                [100.0, 50.0],
+            ),
+            (
+                RadialShape::Ellipse,
+                RadialSize::Explicit(
+                    LengthPercentage::Length(20.0),
+                    LengthPercentage::Percentage(0.5),
+                ),
+                at(100.0, 50.0),
+                [20.0, 50.0],
+            ),
+        ];
+
+        for (shape, size, center, radii) in cases {
+            let gradient = RadialGradient {
+                shape,
+                size,
+                center,
+            };
+            assert_eq!(gradient.radii(200.0, 100.0), radii, "{gradient}");
+        }
+
+        let corner = RadialGradient::default().radii(200.0, 100.0);
+        assert!((corner[0] - 100.0 * std::f32::consts::SQRT_2).abs() < 1e-4);
+        assert!((corner[1] - 50.0 * std::f32::consts::SQRT_2).abs() < 1e-4);
+    }
+}
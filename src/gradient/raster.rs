@@ -0,0 +1,314 @@
+// Software rendering of gradients into pixel buffers.
+use std::f32::consts::PI;
+
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+
+use super::{Gradient, GradientKind};
+
+/// The 4x4 Bayer matrix used for ordered dithering.
+const BAYER: [[f32; 4]; 4] = [
+    [0.0, 8.0, 2.0, 10.0],
+    [12.0, 4.0, 14.0, 6.0],
+    [3.0, 11.0, 1.0, 9.0],
+    [15.0, 7.0, 13.0, 5.0],
+];
+
+/// Options for [`Gradient::rasterize`] and [`Gradient::rasterize_u32`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RasterOptions {
+    /// Add ordered dithering before quantizing to 8 bits, which hides banding in smooth
+    /// gradients.
+    pub dither: bool,
+    /// Write color channels multiplied by alpha, as expected by most compositors.
+    pub premultiplied: bool,
+}
+
+impl Gradient {
+    /// The position along the gradient (as taken by [`Gradient::at`]) of the point `[x, y]` of
+    /// a box of the given size, in pixels from its top left corner.
+    ///
+    /// Linear gradients go from the start to the end of their
+    /// [`GradientCoordinates`](super::GradientCoordinates), resolved for the box with
+    /// [`GradientCoordinates::for_box`](super::GradientCoordinates::for_box).
+    ///
+    /// Radial gradients with a zero-size ending shape are at the position of their last stop
+    /// everywhere, and the other degenerate gradients (e.g. a linear gradient from a point to
+    /// itself) at 0.0.
+    pub fn position_of(&self, [x, y]: [f32; 2], width: f32, height: f32) -> f32 {
+        let t = match &self.kind {
+            GradientKind::Linear(direction) => {
+                let direction = direction.for_box(width, height);
+                let [sx, sy] = [direction.start[0] * width, direction.start[1] * height];
+                let [dx, dy] = [
+                    direction.end[0] * width - sx,
+                    direction.end[1] * height - sy,
+                ];
+                ((x - sx) * dx + (y - sy) * dy) / (dx * dx + dy * dy)
+            }
+            GradientKind::Radial(_) if self.is_zero_size(width, height) => {
+                return self.stops.last().map_or(0.0, |stop| stop.position);
+            }
+            GradientKind::Radial(radial) => {
+                let [cx, cy] = radial.center(width, height);
+                let [rx, ry] = radial.radii(width, height);
+                ((x - cx) / rx).hypot((y - cy) / ry)
+            }
+            GradientKind::Conic(conic) => {
+                let [cx, cy] = conic.center(width, height);
+                // Clockwise from the top, in turns.
+                let angle = (x - cx).atan2(cy - y) / (2.0 * PI);
+                (angle - conic.angle / 360.0).rem_euclid(1.0)
+            }
+        };
+
+        if t.is_finite() { t } else { 0.0 }
+    }
+
+    /// Whether the gradient is a radial gradient whose ending shape has no size, which paints the
+    /// color of its last stop, repeating or not.
+    fn is_zero_size(&self, width: f32, height: f32) -> bool {
+        match &self.kind {
+            GradientKind::Radial(radial) => {
+                let [rx, ry] = radial.radii(width, height);
+                !(rx > 0.0 && ry > 0.0)
+            }
+            _ => false,
+        }
+    }
+
+    /// Fills `pixels`, `width` x `height` pixels of 4 bytes in `[r, g, b, a]` order, row by row
+    /// from the top left corner, with the gradient.
+    ///
+    /// Returns an error when `pixels` is not exactly `width * height * 4` bytes long.
+    ///
+    /// ```
+    /// use colorparser_css::{Gradient, RasterOptions};
+    ///
+    /// let gradient = Gradient::try_from("linear-gradient(to right, black, white)").unwrap();
+    /// let mut pixels = [0; 4 * 4];
+    /// gradient.rasterize(&mut pixels, 4, 1, &RasterOptions::default()).unwrap();
+    ///
+    /// assert_eq!(pixels[..4], [32, 32, 32, 255]);
+    /// ```
+    pub fn rasterize(
+        &self,
+        pixels: &mut [u8],
+        width: usize,
+        height: usize,
+        options: &RasterOptions,
+    ) -> Result<()> {
+        check_size(pixels.len(), width * height * 4)?;
+
+        for (i, pixel) in pixels.chunks_exact_mut(4).enumerate() {
+            pixel.copy_from_slice(&self.pixel(i % width, i / width, width, height, options));
+        }
+
+        Ok(())
+    }
+
+    /// Fills `pixels`, `width` x `height` pixels packed as `0xAARRGGBB`, row by row from the top
+    /// left corner, with the gradient.
+    ///
+    /// Returns an error when `pixels` is not exactly `width * height` long.
+    pub fn rasterize_u32(
+        &self,
+        pixels: &mut [u32],
+        width: usize,
+        height: usize,
+        options: &RasterOptions,
+    ) -> Result<()> {
+        check_size(pixels.len(), width * height)?;
+
+        for (i, pixel) in pixels.iter_mut().enumerate() {
+            let [r, g, b, a] = self.pixel(i % width, i / width, width, height, options);
+            *pixel = u32::from_be_bytes([a, r, g, b]);
+        }
+
+        Ok(())
+    }
+
+    /// The 8 bit color of the pixel at `[x, y]`, sampled at its center.
+    fn pixel(
+        &self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        options: &RasterOptions,
+    ) -> [u8; 4] {
+        let point = [x as f32 + 0.5, y as f32 + 0.5];
+        let (width, height) = (width as f32, height as f32);
+        let color = match self.stops.last() {
+            Some(last) if self.is_zero_size(width, height) => last.color.clone(),
+            _ => self.at(self.position_of(point, width, height)),
+        };
+        let [r, g, b, a] = color.to_array().map(|c| c.clamp(0.0, 1.0));
+
+        let color = match options.premultiplied {
+            true => [r * a, g * a, b * a, a],
+            false => [r, g, b, a],
+        };
+
+        // An offset in [-0.5..0.5) of the 8 bit step.
+        let offset = match options.dither {
+            true => (BAYER[y % 4][x % 4] + 0.5) / 16.0 - 0.5,
+            false => 0.0,
+        };
+
+        color.map(|c| (c * 255.0 + offset).round().clamp(0.0, 255.0) as u8)
+    }
+}
+
+fn check_size(len: usize, expected: usize) -> Result<()> {
+    if len != expected {
+        return Err(Error::new(
+            ErrorKind::InvalidBufferSize,
+            format!("expected {expected} elements, got {len}"),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rasterize(s: &str, width: usize, height: usize, options: RasterOptions) -> Vec<u8> {
+        let mut pixels = vec![0; width * height * 4];
+        Gradient::try_from(s)
+            .unwrap()
+            .rasterize(&mut pixels, width, height, &options)
+            .unwrap();
+        pixels
+    }
+
+    #[test]
+    fn test_rasterize_linear() {
+        let pixels = rasterize(
+            "linear-gradient(to right, black, white)",
+            4,
+            2,
+            RasterOptions::default(),
+        );
+        let row = [32, 96, 159, 223]
+            .iter()
+            .flat_map(|&c| [c, c, c, 255])
+            .collect::<Vec<u8>>();
+        assert_eq!(pixels[..16], row);
+        assert_eq!(pixels[16..], row);
+    }
+
+    #[test]
+    fn test_rasterize_premultiplied() {
+        let s = "linear-gradient(rgb(255 0 0 / 50%), rgb(255 0 0 / 50%))";
+        let straight = rasterize(s, 1, 1, RasterOptions::default());
+        let premultiplied = rasterize(
+            s,
+            1,
+            1,
+            RasterOptions {
+                premultiplied: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(straight, [255, 0, 0, 128]);
+        assert_eq!(premultiplied, [128, 0, 0, 128]);
+    }
+
+    #[test]
+    fn test_rasterize_dither() {
+        let s = "linear-gradient(rgb(127.5 127.5 127.5), rgb(127.5 127.5 127.5))";
+        let options = RasterOptions {
+            dither: true,
+            ..Default::default()
+        };
+        let pixels = rasterize(s, 4, 4, options);
+        let reds = pixels.iter().step_by(4).copied().collect::<Vec<u8>>();
+
+        assert!(reds.iter().all(|&r| r == 127 || r == 128), "{reds:?}");
+        assert!(reds.contains(&127) && reds.contains(&128), "{reds:?}");
+        assert!(pixels.iter().skip(3).step_by(4).all(|&a| a == 255));
+    }
+
+    #[test]
+    fn test_rasterize_zero_size() {
+        for s in [
+            "radial-gradient(circle 0px, red, blue)",
+            "repeating-radial-gradient(circle 0px, red, blue)",
+            "repeating-radial-gradient(0px 10px at 0 0, red, lime 50%, blue)",
+        ] {
+            let pixels = rasterize(s, 3, 2, RasterOptions::default());
+            assert!(
+                pixels.chunks(4).all(|pixel| pixel == [0, 0, 255, 255]),
+                "{s}: {pixels:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_rasterize_u32() {
+        let gradient = Gradient::try_from("linear-gradient(#ff8000, #ff8000)").unwrap();
+        let mut pixels = [0u32; 2];
+        gradient
+            .rasterize_u32(&mut pixels, 2, 1, &RasterOptions::default())
+            .unwrap();
+        assert_eq!(pixels, [0xffff8000; 2]);
+
+        assert_eq!(
+            gradient
+                .rasterize_u32(&mut pixels, 2, 2, &RasterOptions::default())
+                .unwrap_err()
+                .kind(),
+            ErrorKind::InvalidBufferSize
+        );
+        assert!(
+            gradient
+                .rasterize(&mut [0; 7], 2, 1, &RasterOptions::default())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_position_of() {
+        let radial = Gradient::try_from("radial-gradient(circle 10px at 0 0, red, blue)").unwrap();
+        assert_eq!(radial.position_of([0.0, 0.0], 20.0, 20.0), 0.0);
+        assert_eq!(radial.position_of([6.0, 8.0], 20.0, 20.0), 1.0);
+
+        let ellipse = Gradient::try_from("radial-gradient(closest-side, red, blue)").unwrap();
+        assert_eq!(ellipse.position_of([20.0, 5.0], 20.0, 10.0), 1.0);
+        assert_eq!(ellipse.position_of([10.0, 10.0], 20.0, 10.0), 1.0);
+
+        let conic = Gradient::try_from("conic-gradient(red, blue)").unwrap();
+        let cases = [
+            ([1.5, 0.5], 0.125),
+            ([1.5, 1.5], 0.375),
+            ([0.5, 1.5], 0.625),
+            ([0.5, 0.5], 0.875),
+        ];
+        for (point, t) in cases {
+            assert!(
+                (conic.position_of(point, 2.0, 2.0) - t).abs() < 1e-6,
+                "{point:?}"
+            );
+        }
+
+        let rotated = Gradient::try_from("conic-gradient(from 90deg, red, blue)").unwrap();
+        assert!((rotated.position_of([1.5, 1.5], 2.0, 2.0) - 0.125).abs() < 1e-6);
+
+        let point = Gradient::try_from("radial-gradient(circle 0px, red, blue 80%)").unwrap();
+        assert_eq!(point.position_of([1.0, 1.0], 2.0, 2.0), 0.8);
+        assert_eq!(point.position_of([0.0, 1.0], 2.0, 2.0), 0.8);
+
+        let line = Gradient::try_from("linear-gradient(red, blue)").unwrap();
+        assert_eq!(line.position_of([1.0, 1.0], 0.0, 0.0), 0.0);
+
+        // The other corners of `to <corner>` are on the 50% line, whatever the aspect ratio.
+        let corner = Gradient::try_from("linear-gradient(to top right, red, blue)").unwrap();
+        assert!((corner.position_of([0.0, 0.0], 200.0, 100.0) - 0.5).abs() < 1e-5);
+        assert!((corner.position_of([200.0, 100.0], 200.0, 100.0) - 0.5).abs() < 1e-5);
+        assert!(corner.position_of([0.0, 100.0], 200.0, 100.0).abs() < 1e-5);
+    }
+}
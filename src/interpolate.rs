@@ -0,0 +1,73 @@
+use core::fmt;
+
+use crate::Error;
+use crate::ErrorKind;
+use crate::utils::normalize_angle;
+
+/// How hue angles are interpolated in polar color spaces (e.g. OKLCH or HSL), as defined by
+/// [CSS Color 4](https://www.w3.org/TR/css-color-4/#hue-interpolation).
+#[cfg_attr(feature = "schema", derive(schema_jsonrs::JsonSchema))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HueInterpolation {
+    /// Take the shorter arc between the two hues (the default).
+    #[default]
+    Shorter,
+    /// Take the longer arc between the two hues.
+    Longer,
+    /// Always go through increasing hue angles.
+    Increasing,
+    /// Always go through decreasing hue angles.
+    Decreasing,
+}
+
+impl HueInterpolation {
+    /// The CSS keyword of this method, as used in `<hue-interpolation-method>`.
+    pub fn css_name(&self) -> &'static str {
+        match self {
+            Self::Shorter => "shorter",
+            Self::Longer => "longer",
+            Self::Increasing => "increasing",
+            Self::Decreasing => "decreasing",
+        }
+    }
+
+    /// Interpolate between two hue angles (in degrees). `t` in the range [0..1].
+    ///
+    /// Returns a hue angle in the range [0..360).
+    pub fn interpolate(&self, h1: f32, h2: f32, t: f32) -> f32 {
+        let (mut h1, mut h2) = (normalize_angle(h1), normalize_angle(h2));
+        let delta = h2 - h1;
+
+        match self {
+            Self::Shorter if delta > 180.0 => h1 += 360.0,
+            Self::Shorter if delta < -180.0 => h2 += 360.0,
+            Self::Longer if 0.0 < delta && delta < 180.0 => h1 += 360.0,
+            Self::Longer if -180.0 < delta && delta <= 0.0 => h2 += 360.0,
+            Self::Increasing if delta < 0.0 => h2 += 360.0,
+            Self::Decreasing if delta > 0.0 => h1 += 360.0,
+            _ => {}
+        }
+
+        normalize_angle(h1 + t * (h2 - h1))
+    }
+}
+
+impl TryFrom<&str> for HueInterpolation {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "shorter" => Ok(Self::Shorter),
+            "longer" => Ok(Self::Longer),
+            "increasing" => Ok(Self::Increasing),
+            "decreasing" => Ok(Self::Decreasing),
+            _ => Err(Error::new(ErrorKind::InvalidHueInterpolation, s)),
+        }
+    }
+}
+
+impl fmt::Display for HueInterpolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.css_name())
+    }
+}
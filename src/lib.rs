@@ -5,6 +5,10 @@
 //! ## Supported Color Format
 //!
 //! * [Named colors](https://www.w3.org/TR/css-color-4/#named-colors)
+//! * [System colors](https://www.w3.org/TR/css-color-4/#css-system-colors) (e.g. `Canvas`,
+//!   `AccentColor` or `Highlight`) and the `accent`/`accent_inactive` keywords, resolved by the
+//!   installed [`SystemColorProvider`]. [`DesktopSystemColors`] reads the accent color of KDE
+//!   and GTK desktops
 //! * RGB hexadecimal (with and without `#` prefix)
 //!      + Short format `#rgb`
 //!      + Short format with alpha `#rgba`
@@ -12,7 +16,43 @@
 //!      + Long format with alpha `#rrggbbaa`
 //! * `rgb()` and `rgba()`
 //! * `hsl()` and `hsla()`
-//! * `gradient()`
+//! * `hwb()`
+//! * `lab()` and `lch()`
+//! * `oklab()` and `oklch()`
+//! * `color()` with the predefined color spaces `srgb`, `srgb-linear`, `display-p3`, `a98-rgb`,
+//!   `prophoto-rgb`, `rec2020`, `xyz-d50` and `xyz-d65` (or `xyz`). Colors outside the sRGB
+//!   gamut are kept as they are, unless [`ParseOptions::gamut_map`] is set, see
+//!   [`Solid::gamut_map`].
+//! * `color-mix()` in any of the above color spaces, plus `hsl`, `hwb`, `lab`, `lch`, `oklab` and
+//!   `oklch`, with an optional hue interpolation method (see [`Solid::interpolate`])
+//! * The `none` keyword in every channel, for components that are missing when interpolating
+//!   (see [`Solid::is_missing`])
+//! * Math functions in channels: `calc()`, `min()`, `max()`, `clamp()`, `round()`, `mod()`,
+//!   `rem()`, `abs()`, `sign()`, `sin()`, `cos()`, `tan()`, `asin()`, `acos()`, `atan()`,
+//!   `atan2()`, `pow()`, `sqrt()`, `hypot()`, `log()` and `exp()`, with the `e` and `pi`
+//!   constants
+//! * Relative colors, e.g. `hsl(from <color> h s calc(l - 10%))`, in every color function but
+//!   `color-mix()`. The origin color may use any supported syntax, including theme keys
+//! * `light-dark()`, resolved against [`ParseOptions::color_scheme`]
+//! * `currentColor` and `var(--name, <fallback>)`, resolved against
+//!   [`ParseOptions::current_color`] and [`ParseOptions::variables`], in colors and gradients
+//! * Sass/Less color adjustments: `darken()`, `lighten()`, `saturate()`, `desaturate()`,
+//!   `adjust-hue()`/`spin()`, `grayscale()`, `complement()`, `invert()`, `fade()`, `fadein()`,
+//!   `fadeout()`, `tint()`, `shade()` and `mix()`
+//! * `gradient()`, with evenly spaced colors
+//! * `linear-gradient()` with color stop positions, including double-position stops (see
+//!   [`GradientStop`])
+//! * `radial-gradient()` with a circle or ellipse shape, size keywords or lengths, and a center
+//!   position (see [`RadialGradient`])
+//! * `conic-gradient()` with a start angle, a center position and stops in angles or
+//!   percentages (see [`ConicGradient`])
+//! * `repeating-linear-gradient()`, `repeating-radial-gradient()` and
+//!   `repeating-conic-gradient()` (see [`Gradient::repeat_position`])
+//! * Gradient interpolation methods, e.g. `linear-gradient(in oklch longer hue, red, blue)`, in
+//!   the same color spaces as `color-mix()` (see [`Gradient::interpolate`])
+//! * Color hints between gradient stops, e.g. `linear-gradient(red, 30%, blue)`, and as an
+//!   extension, easing functions such as `ease-in-out`, `cubic-bezier()` and `steps()` in their
+//!   place (see [`Easing`])
 //!
 //! ### Example Color Format
 //!
@@ -26,6 +66,10 @@
 //! lime
 //! accent
 //! accent_inactive
+//! AccentColor
+//! CanvasText
+//! currentColor
+//! var(--accent, #89b4fa)
 //! #0f0
 //! #0f0f
 //! #00ff00
@@ -42,7 +86,44 @@
 //! hsl(133.333grad 100% 50%)
 //! hsl(2.0944rad 100% 50%)
 //! hsla(120,100%,50%,100%)
+//! hsl(none 0% 50%)
+//! hwb(120 0% 0%)
+//! hwb(194 0% 0% / .5)
+//! hwb(none 50% 50%)
+//! lab(52.2% 40.1 59.9)
+//! lab(29.2345% 39.3825 20.0664 / 50%)
+//! lch(52.2% 72.2 56.2)
+//! lch(67.5345 42.5 258.2deg)
+//! oklab(59.686% 0.1009 0.1192)
+//! oklab(0.65125 -0.0320 0.1274 / 50%)
+//! oklch(60% 0.15 50)
+//! oklch(0.7 40% 0.25turn)
+//! color(srgb 0 1 0)
+//! color(display-p3 1 0.5 0 / 50%)
+//! color(rec2020 0.42 0.97 0.3)
+//! color(xyz-d65 0.358 0.715 0.119)
+//! hsl(calc(120 + 45) 80% 50%)
+//! rgb(min(255, 300) calc(255 / 3) 0 / clamp(0, 0.8, 1))
+//! oklch(0.7 0.1 calc(0.25turn * 2))
+//! rgb(from #89b4fa r g b / 50%)
+//! hsl(from accent h s calc(l - 10%))
+//! oklch(from red l c calc(h + 180))
+//! color(from red display-p3 r g b)
+//! color-mix(in srgb, red, blue)
+//! color-mix(in oklch, #89b4fa 30%, white)
+//! color-mix(in hsl longer hue, 25% red, blue)
+//! light-dark(#eff1f5, #1e1e2e)
+//! darken(#89b4fa, 10%)
+//! lighten(accent, 15%)
+//! spin(#89b4fa, 180deg)
+//! mix(red, darken(blue, 10%), 25%)
 //! gradient(rgb(0, 255, 0), #0f0, to right)
+//! linear-gradient(45deg, red 10%, blue 40% 60%, green)
+//! radial-gradient(circle closest-side at 30% 40%, #89b4fa, transparent)
+//! conic-gradient(from 90deg at 50% 50%, red, blue 0.25turn, red)
+//! repeating-linear-gradient(45deg, #f9e2af 0% 5%, #11111b 5% 10%)
+//! linear-gradient(to right in oklch longer hue, red, blue)
+//! linear-gradient(red, 30%, blue, steps(4), green)
 //! ```
 //! </details>
 //!
@@ -51,7 +132,7 @@
 //! Add this to your `Cargo.toml`
 //!
 //! ```toml
-//! colorparser_css = "0.1.0"
+//! colorparser_css = "0.2.0"
 //! ```
 //!
 //! ## Default Feature
@@ -61,33 +142,63 @@
 //!
 //! ## Optional Features
 //!
-//! * `serde`: Enables serializing (into HEX string) and deserializing (from any supported string color format) using [`serde`](https://serde.rs/) framework.
+//! * `serde`: Enables serializing (into CSS string, see [`Solid::to_css_string`] and [`Gradient::to_css_string`]) and deserializing (from any supported string color format) using [`serde`](https://serde.rs/) framework. Colors are serialized into HEX strings when that loses nothing, and every serialized value parses back to the same value.
 
 mod color;
 mod colorspace;
 mod error;
 mod gradient;
+mod interpolate;
 mod parser;
 mod solid;
+mod system_color;
 #[cfg(any(feature = "theme", feature = "theme_yml"))]
 mod theme;
 mod utils;
 
 pub use color::Color;
 pub use color::ColorValue;
+pub use colorspace::ColorComponent;
+pub use colorspace::Colorspace;
 pub use colorspace::ColorspaceImpl;
 pub use colorspace::Hsla;
+pub use colorspace::Hwba;
+pub use colorspace::Lab;
+pub use colorspace::Lch;
 pub use colorspace::NormalizedHsla;
 pub use colorspace::NormalizedRgba;
+pub use colorspace::Oklab;
+pub use colorspace::Oklch;
 pub use colorspace::Rgba;
 pub use colorspace::Rgba16;
 pub use error::Error;
 pub use error::ErrorKind;
 pub use error::Result;
+pub use gradient::ConicGradient;
+pub use gradient::Easing;
 pub use gradient::Gradient;
 pub use gradient::GradientCoordinates;
+pub use gradient::GradientKind;
+pub use gradient::GradientStop;
+pub use gradient::LengthPercentage;
+pub use gradient::LinearDirection;
+pub use gradient::RasterOptions;
+pub use gradient::StepPosition;
+pub use gradient::{RadialGradient, RadialShape, RadialSize};
+pub use interpolate::HueInterpolation;
+pub use parser::ColorScheme;
+pub use parser::ParseOptions;
 pub use parser::parse;
+pub use parser::parse_with_options;
 pub use solid::Solid;
+pub use system_color::AccentSource;
+pub use system_color::DefaultSystemColors;
+pub use system_color::DesktopSystemColors;
+pub use system_color::SystemColor;
+pub use system_color::SystemColorProvider;
+pub use system_color::reset_system_color_provider;
+pub use system_color::set_system_color_provider;
+pub use system_color::system_color_provider;
 
 #[cfg(feature = "named-colors")]
 pub use parser::NAMED_COLORS;
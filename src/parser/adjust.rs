@@ -0,0 +1,154 @@
+// Sass/Less-style color adjustment functions, e.g. `darken(#89b4fa, 10%)`.
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+use crate::Solid;
+use crate::utils::split_args;
+
+use super::ParseOptions;
+use super::parse_angle;
+use super::parse_percent_or_float;
+use super::parse_solid_with_options;
+
+/// Parses a color adjustment function call such as `darken(<color>, <amount>)` or
+/// `mix(<color>, <color>, <weight>)`. `fname` must be one of the supported function names.
+///
+/// Color arguments accept every syntax `parse_solid` does, including other adjustment
+/// functions, theme keys and `accent`.
+pub fn parse_adjust(
+    fname: &str,
+    args: &str,
+    original_s: &str,
+    options: &ParseOptions,
+) -> Result<Solid> {
+    let err = || Error::new(error_kind(fname), original_s);
+    let color = |s: &str| -> Result<Solid> {
+        if s.is_empty() {
+            return Err(err());
+        }
+        parse_solid_with_options(s, options)
+    };
+    let amount = |s: &str| parse_amount(s).ok_or_else(err);
+    let alpha = |s: &str| {
+        parse_percent_or_float(s)
+            .map(|(t, _)| t)
+            .filter(|t| (0.0..=1.0).contains(t))
+            .ok_or_else(err)
+    };
+
+    match (fname, split_args(args).as_slice()) {
+        ("darken", [c, t]) => Ok(color(c)?.darken(amount(t)?)),
+        ("lighten", [c, t]) => Ok(color(c)?.lighten(amount(t)?)),
+        ("saturate", [c, t]) => Ok(color(c)?.saturate(amount(t)?)),
+        ("desaturate", [c, t]) => Ok(color(c)?.desaturate(amount(t)?)),
+        ("adjust-hue" | "spin", [c, t]) => {
+            Ok(color(c)?.adjust_hue(parse_angle(t).ok_or_else(err)?))
+        }
+        ("grayscale", [c]) => Ok(color(c)?.grayscale()),
+        ("complement", [c]) => Ok(color(c)?.complement()),
+        ("invert", [c]) => Ok(color(c)?.invert(1.0)),
+        ("invert", [c, w]) => Ok(color(c)?.invert(amount(w)?)),
+        ("fade", [c, t]) => Ok(color(c)?.fade(alpha(t)?)),
+        ("fadein", [c, t]) => Ok(color(c)?.fade_in(alpha(t)?)),
+        ("fadeout", [c, t]) => Ok(color(c)?.fade_out(alpha(t)?)),
+        ("tint", [c]) => Ok(color(c)?.tint(0.5)),
+        ("tint", [c, w]) => Ok(color(c)?.tint(amount(w)?)),
+        ("shade", [c]) => Ok(color(c)?.shade(0.5)),
+        ("shade", [c, w]) => Ok(color(c)?.shade(amount(w)?)),
+        ("mix", [c1, c2]) => Ok(color(c1)?.mix(&color(c2)?, 0.5)),
+        ("mix", [c1, c2, w]) => Ok(color(c1)?.mix(&color(c2)?, amount(w)?)),
+        _ => Err(err()),
+    }
+}
+
+fn error_kind(fname: &str) -> ErrorKind {
+    match fname {
+        "darken" => ErrorKind::InvalidDarken,
+        "lighten" => ErrorKind::InvalidLighten,
+        "saturate" => ErrorKind::InvalidSaturate,
+        "desaturate" => ErrorKind::InvalidDesaturate,
+        "adjust-hue" | "spin" => ErrorKind::InvalidAdjustHue,
+        "grayscale" => ErrorKind::InvalidGrayscale,
+        "complement" => ErrorKind::InvalidComplement,
+        "invert" => ErrorKind::InvalidInvert,
+        "fade" | "fadein" | "fadeout" => ErrorKind::InvalidFade,
+        "tint" => ErrorKind::InvalidTint,
+        "shade" => ErrorKind::InvalidShade,
+        "mix" => ErrorKind::InvalidMix,
+        _ => ErrorKind::InvalidFunction,
+    }
+}
+
+/// Parses a Sass amount. Both `10%` and `10` mean ten percent; the result is in the range [0..1].
+fn parse_amount(s: &str) -> Option<f32> {
+    let num = s.strip_suffix('%').unwrap_or(s).trim_end();
+    let amount = num.parse::<f32>().ok()? / 100.0;
+
+    (0.0..=1.0).contains(&amount).then_some(amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_solid;
+
+    #[test]
+    fn test_darken() {
+        let color = parse_solid("darken(#89b4fa, 10%)", None).unwrap();
+        let hsla = Solid::from_html("#89b4fa").unwrap().to_normalized_hsla();
+
+        assert_eq!(
+            color.to_rgba(),
+            Solid::from_normalized_hsla(hsla.h, hsla.s, hsla.l - 0.1, hsla.a).to_rgba()
+        );
+    }
+
+    #[test]
+    fn test_lighten_nested() {
+        let color = parse_solid("lighten(darken(rgb(255, 0, 0), 20%), 20%)", None).unwrap();
+
+        assert_eq!(color.to_hex_string(), "#ff0000");
+    }
+
+    #[test]
+    fn test_adjustments() {
+        let cases = [
+            ("complement(#ff0000)", "#00ffff"),
+            ("spin(#ff0000, 120deg)", "#00ff00"),
+            ("adjust-hue(#ff0000, -120)", "#0000ff"),
+            ("grayscale(#ff0000)", "#808080"),
+            ("desaturate(#ff0000, 100%)", "#808080"),
+            ("saturate(grayscale(#ff0000), 100%)", "#ff0000"),
+            ("invert(#ff0000)", "#00ffff"),
+            ("invert(#ff0000, 50%)", "#808080"),
+            ("fade(#ff0000, 50%)", "#ff000080"),
+            ("fadeout(#ff0000, 0.5)", "#ff000080"),
+            ("fadein(fade(#ff0000, 25%), 25%)", "#ff000080"),
+            ("tint(#000000)", "#808080"),
+            ("shade(#ffffff, 100%)", "#000000"),
+            ("mix(#ff0000, #0000ff)", "#800080"),
+            ("mix(#ff0000, #0000ff, 25%)", "#4000bf"),
+            ("mix(mix(red, blue), rgb(0, 255, 0), 0%)", "#00ff00"),
+        ];
+
+        for (s, hex) in cases {
+            assert_eq!(parse_solid(s, None).unwrap().to_hex_string(), hex, "{s}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let cases = [
+            ("darken(#89b4fa, 120%)", ErrorKind::InvalidDarken),
+            ("lighten(#89b4fa)", ErrorKind::InvalidLighten),
+            ("spin(#89b4fa, 10px)", ErrorKind::InvalidAdjustHue),
+            ("grayscale()", ErrorKind::InvalidGrayscale),
+            ("fade(#89b4fa, 2)", ErrorKind::InvalidFade),
+            ("mix(#89b4fa)", ErrorKind::InvalidMix),
+        ];
+
+        for (s, kind) in cases {
+            assert_eq!(parse_solid(s, None).unwrap_err().kind(), kind, "{s}");
+        }
+    }
+}
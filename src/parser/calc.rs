@@ -0,0 +1,486 @@
+// Evaluation of CSS math functions in color channels, e.g. `calc(l - 10%)` or `min(50%, 120)`.
+use std::borrow::Cow;
+use std::f32::consts::{E, PI};
+
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+
+/// The CSS math functions that may be used as a channel argument.
+const MATH_FUNCTIONS: [&str; 21] = [
+    "calc", "min", "max", "clamp", "round", "mod", "rem", "abs", "sign", "sin", "cos", "tan",
+    "asin", "acos", "atan", "atan2", "pow", "sqrt", "hypot", "log", "exp",
+];
+
+/// The type of a math expression.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Unit {
+    Number,
+    /// A percentage, `50%` has the value `50.0`.
+    Percent,
+    /// An angle, in degrees.
+    Angle,
+}
+
+/// The result of a math expression.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Value {
+    pub value: f32,
+    pub unit: Unit,
+}
+
+impl Value {
+    fn new(value: f32, unit: Unit) -> Self {
+        Self { value, unit }
+    }
+
+    fn number(value: f32) -> Self {
+        Self::new(value, Unit::Number)
+    }
+
+    /// Resolves the value of a channel to a plain number.
+    ///
+    /// Numeric channels pass the reference their percentages resolve against (e.g. 255 for
+    /// `rgb()`) and do not accept angles. Hue channels pass `None`: they accept numbers and
+    /// angles (both in degrees) but no percentages.
+    pub fn resolve(self, reference: Option<f32>) -> Option<f32> {
+        match (self.unit, reference) {
+            (Unit::Number, _) => Some(self.value),
+            (Unit::Percent, Some(reference)) => Some(self.value / 100.0 * reference),
+            (Unit::Angle, None) => Some(self.value),
+            _ => None,
+        }
+    }
+
+    /// Formats the value as a CSS literal, e.g. `50%` or `120deg`.
+    fn to_css_string(self) -> String {
+        match self.unit {
+            Unit::Number => format!("{}", self.value),
+            Unit::Percent => format!("{}%", self.value),
+            Unit::Angle => format!("{}deg", self.value),
+        }
+    }
+}
+
+/// Whether `s` is a math function call, e.g. `calc(120 + 45)`.
+pub fn is_math_function(s: &str) -> bool {
+    s.split_once('(')
+        .is_some_and(|(name, _)| MATH_FUNCTIONS.contains(&name.trim_end()))
+}
+
+/// Replaces a math function by the literal it evaluates to (e.g. `calc(120 + 45)` by `165`),
+/// so the channel parsers only have to deal with numbers, percentages and angles.
+/// Other arguments are returned as they are.
+pub fn resolve_math_function(s: &str) -> Result<Cow<'_, str>> {
+    if !is_math_function(s) {
+        return Ok(Cow::Borrowed(s));
+    }
+
+    eval(s, &[], None).map(|value| Cow::Owned(value.to_css_string()))
+}
+
+/// Evaluates a math expression: a number, a percentage, an angle, a variable (e.g. a channel
+/// keyword of a relative color) or a math function combining them.
+///
+/// Numbers and percentages can only be added together when `percent_reference` is given, in
+/// which case the percentages resolve against it (e.g. `calc(l - 10%)` in `hsl()` with a
+/// reference of 100).
+///
+/// Returns [`ErrorKind::InvalidCalc`] when the expression is malformed and
+/// [`ErrorKind::IncompatibleUnits`] when it combines values of incompatible types.
+pub fn eval(s: &str, vars: &[(&str, f32)], percent_reference: Option<f32>) -> Result<Value> {
+    let mut parser = Parser {
+        input: s,
+        s: s.as_bytes(),
+        pos: 0,
+        vars,
+        percent_reference,
+    };
+
+    let value = parser.parse_sum()?;
+    parser.skip_whitespace();
+
+    if parser.pos != parser.s.len() || !value.value.is_finite() {
+        return Err(parser.error());
+    }
+
+    Ok(value)
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    s: &'a [u8],
+    pos: usize,
+    vars: &'a [(&'a str, f32)],
+    percent_reference: Option<f32>,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self) -> Error {
+        Error::new(ErrorKind::InvalidCalc, self.input)
+    }
+
+    fn unit_error(&self) -> Error {
+        Error::new(ErrorKind::IncompatibleUnits, self.input)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        self.skip_whitespace();
+        let found = self.peek() == Some(c);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, c: u8) -> Result<()> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    /// Brings two values to the same unit, for sums and comparisons.
+    fn unify(&self, a: Value, b: Value) -> Result<(Value, Value)> {
+        if a.unit == b.unit {
+            return Ok((a, b));
+        }
+
+        let to_number = |v: Value, reference: f32| match v.unit {
+            Unit::Percent => Some(Value::number(v.value / 100.0 * reference)),
+            Unit::Number => Some(v),
+            Unit::Angle => None,
+        };
+
+        self.percent_reference
+            .and_then(|r| Some((to_number(a, r)?, to_number(b, r)?)))
+            .ok_or_else(|| self.unit_error())
+    }
+
+    /// `<product> [ ('+' | '-') <product> ]*`
+    fn parse_sum(&mut self) -> Result<Value> {
+        let mut value = self.parse_product()?;
+
+        loop {
+            let sign = if self.eat(b'+') {
+                1.0
+            } else if self.eat(b'-') {
+                -1.0
+            } else {
+                return Ok(value);
+            };
+
+            let rhs = self.parse_product()?;
+            let (a, b) = self.unify(value, rhs)?;
+            value = Value::new(a.value + sign * b.value, a.unit);
+        }
+    }
+
+    /// `<value> [ ('*' | '/') <value> ]*`
+    fn parse_product(&mut self) -> Result<Value> {
+        let mut value = self.parse_value()?;
+
+        loop {
+            if self.eat(b'*') {
+                let rhs = self.parse_value()?;
+                value = match (value.unit, rhs.unit) {
+                    (Unit::Number, unit) | (unit, Unit::Number) => {
+                        Value::new(value.value * rhs.value, unit)
+                    }
+                    _ => return Err(self.unit_error()),
+                };
+            } else if self.eat(b'/') {
+                let rhs = self.parse_value()?;
+                value = match (value.unit, rhs.unit) {
+                    (unit, Unit::Number) => Value::new(value.value / rhs.value, unit),
+                    (a, b) if a == b => Value::number(value.value / rhs.value),
+                    _ => return Err(self.unit_error()),
+                };
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// A number, a dimension, a keyword, a math function or `(<sum>)`, optionally negated.
+    fn parse_value(&mut self) -> Result<Value> {
+        self.skip_whitespace();
+
+        match self.peek().ok_or_else(|| self.error())? {
+            b'-' | b'+' if !self.starts_number() => {
+                let sign = if self.s[self.pos] == b'-' { -1.0 } else { 1.0 };
+                self.pos += 1;
+                self.parse_value()
+                    .map(|v| Value::new(sign * v.value, v.unit))
+            }
+            b'(' => {
+                self.pos += 1;
+                let value = self.parse_sum()?;
+                self.expect(b')')?;
+                Ok(value)
+            }
+            c if c.is_ascii_alphabetic() => {
+                let word = self.take_while(|c| c.is_ascii_alphanumeric() || c == b'-');
+
+                if self.eat(b'(') {
+                    let value = self.parse_function(word)?;
+                    self.expect(b')')?;
+                    return Ok(value);
+                }
+
+                match word {
+                    "e" => Ok(Value::number(E)),
+                    "pi" => Ok(Value::number(PI)),
+                    _ => self
+                        .vars
+                        .iter()
+                        .find(|(name, _)| *name == word)
+                        .map(|(_, t)| Value::number(*t))
+                        .ok_or_else(|| self.error()),
+                }
+            }
+            _ => self.parse_dimension(),
+        }
+    }
+
+    /// Parses the comma separated arguments of a math function, up to the closing parenthesis.
+    fn parse_function(&mut self, name: &str) -> Result<Value> {
+        let strategy = match name {
+            "round" => self.parse_rounding_strategy(),
+            _ => None,
+        };
+
+        let mut args = vec![self.parse_sum()?];
+        while self.eat(b',') {
+            args.push(self.parse_sum()?);
+        }
+
+        let number = |v: &Value| {
+            (v.unit == Unit::Number)
+                .then_some(v.value)
+                .ok_or_else(|| self.unit_error())
+        };
+        let radians = |v: &Value| match v.unit {
+            Unit::Number => Ok(v.value),
+            Unit::Angle => Ok(v.value.to_radians()),
+            Unit::Percent => Err(self.unit_error()),
+        };
+        let degrees = |t: f32| Ok(Value::new(t.to_degrees(), Unit::Angle));
+
+        match (name, args.as_slice()) {
+            ("calc", [a]) => Ok(*a),
+            ("min" | "max" | "hypot", [first, rest @ ..]) => {
+                rest.iter().try_fold(*first, |acc, v| {
+                    let (a, b) = self.unify(acc, *v)?;
+                    let value = match name {
+                        "min" => a.value.min(b.value),
+                        "max" => a.value.max(b.value),
+                        _ => a.value.hypot(b.value),
+                    };
+                    Ok(Value::new(value, a.unit))
+                })
+            }
+            ("clamp", [min, value, max]) => {
+                let (min, value) = self.unify(*min, *value)?;
+                let (value, max) = self.unify(value, *max)?;
+                Ok(Value::new(
+                    value.value.min(max.value).max(min.value),
+                    value.unit,
+                ))
+            }
+            ("round", [a]) if a.unit == Unit::Number => {
+                Ok(Value::number(round(strategy, a.value, 1.0)))
+            }
+            ("round", [a, b]) => {
+                let (a, b) = self.unify(*a, *b)?;
+                Ok(Value::new(round(strategy, a.value, b.value), a.unit))
+            }
+            ("mod" | "rem", [a, b]) => {
+                let (a, b) = self.unify(*a, *b)?;
+                let q = a.value / b.value;
+                let q = if name == "mod" { q.floor() } else { q.trunc() };
+                Ok(Value::new(a.value - b.value * q, a.unit))
+            }
+            ("abs", [a]) => Ok(Value::new(a.value.abs(), a.unit)),
+            ("sign", [a]) => Ok(Value::number(if a.value == 0.0 {
+                0.0
+            } else {
+                a.value.signum()
+            })),
+            ("sin", [a]) => Ok(Value::number(radians(a)?.sin())),
+            ("cos", [a]) => Ok(Value::number(radians(a)?.cos())),
+            ("tan", [a]) => Ok(Value::number(radians(a)?.tan())),
+            ("asin", [a]) => degrees(number(a)?.asin()),
+            ("acos", [a]) => degrees(number(a)?.acos()),
+            ("atan", [a]) => degrees(number(a)?.atan()),
+            ("atan2", [a, b]) => {
+                let (a, b) = self.unify(*a, *b)?;
+                degrees(a.value.atan2(b.value))
+            }
+            ("pow", [a, b]) => Ok(Value::number(number(a)?.powf(number(b)?))),
+            ("sqrt", [a]) => Ok(Value::number(number(a)?.sqrt())),
+            ("exp", [a]) => Ok(Value::number(number(a)?.exp())),
+            ("log", [a]) => Ok(Value::number(number(a)?.ln())),
+            ("log", [a, base]) => Ok(Value::number(number(a)?.log(number(base)?))),
+            _ => Err(self.error()),
+        }
+    }
+
+    /// Parses the optional `<rounding-strategy>` of `round()`, with its trailing comma.
+    fn parse_rounding_strategy(&mut self) -> Option<&'a str> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let word = self.take_while(|c| c.is_ascii_alphabetic() || c == b'-');
+
+        if matches!(word, "nearest" | "up" | "down" | "to-zero") && self.eat(b',') {
+            return Some(word);
+        }
+
+        self.pos = start;
+        None
+    }
+
+    fn starts_number(&self) -> bool {
+        self.s
+            .get(self.pos + 1)
+            .is_some_and(|c| c.is_ascii_digit() || *c == b'.')
+    }
+
+    fn parse_dimension(&mut self) -> Result<Value> {
+        let start = self.pos;
+        if matches!(self.peek(), Some(b'-' | b'+')) {
+            self.pos += 1;
+        }
+        let num_start = self.pos;
+        self.take_while(|c| c.is_ascii_digit() || c == b'.');
+        if matches!(self.peek(), Some(b'e' | b'E'))
+            && self
+                .s
+                .get(self.pos + 1)
+                .is_some_and(|c| c.is_ascii_digit() || *c == b'-' || *c == b'+')
+        {
+            self.pos += 2;
+            self.take_while(|c| c.is_ascii_digit());
+        }
+        if self.pos == num_start {
+            return Err(self.error());
+        }
+
+        let value = self.input[start..self.pos]
+            .parse::<f32>()
+            .map_err(|_| self.error())?;
+        let unit = self.take_while(|c| c.is_ascii_alphabetic() || c == b'%');
+
+        match unit {
+            "" => Ok(Value::number(value)),
+            "%" => Ok(Value::new(value, Unit::Percent)),
+            "deg" => Ok(Value::new(value, Unit::Angle)),
+            "grad" => Ok(Value::new(value * 360.0 / 400.0, Unit::Angle)),
+            "rad" => Ok(Value::new(value.to_degrees(), Unit::Angle)),
+            "turn" => Ok(Value::new(value * 360.0, Unit::Angle)),
+            _ => Err(self.unit_error()),
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(u8) -> bool) -> &'a str {
+        let start = self.pos;
+        while self.peek().is_some_and(&f) {
+            self.pos += 1;
+        }
+        &self.input[start..self.pos]
+    }
+}
+
+/// `round(<strategy>, a, b)`: rounds `a` to a multiple of `b`.
+fn round(strategy: Option<&str>, a: f32, b: f32) -> f32 {
+    let q = a / b;
+    let q = match strategy {
+        Some("up") => q.ceil(),
+        Some("down") => q.floor(),
+        Some("to-zero") => q.trunc(),
+        // CSS rounds half-way values up, towards positive infinity.
+        _ => (q + 0.5).floor(),
+    };
+
+    q * b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let cases = [
+            ("calc(120 + 45)", 165.0, Unit::Number),
+            ("calc(2 * (1 + 2) - -1)", 7.0, Unit::Number),
+            ("calc(10% * 3)", 30.0, Unit::Percent),
+            ("calc(1turn / 4)", 90.0, Unit::Angle),
+            ("min(50%, 20%, 70%)", 20.0, Unit::Percent),
+            ("max(1, 2)", 2.0, Unit::Number),
+            ("clamp(10%, 120%, 100%)", 100.0, Unit::Percent),
+            ("round(12.5)", 13.0, Unit::Number),
+            ("round(down, 17, 5)", 15.0, Unit::Number),
+            ("round(33deg, 10deg)", 30.0, Unit::Angle),
+            ("mod(-370deg, 360deg)", 350.0, Unit::Angle),
+            ("rem(-370deg, 360deg)", -10.0, Unit::Angle),
+            ("abs(-2)", 2.0, Unit::Number),
+            ("calc(sin(90deg) * 100)", 100.0, Unit::Number),
+            ("atan2(1, 1)", 45.0, Unit::Angle),
+            ("pow(2, 3)", 8.0, Unit::Number),
+            ("calc(sqrt(16) + hypot(3, 4))", 9.0, Unit::Number),
+        ];
+
+        for (s, value, unit) in cases {
+            let result = eval(s, &[], None).unwrap();
+            assert!((result.value - value).abs() < 1e-4, "{s}: {result:?}");
+            assert_eq!(result.unit, unit, "{s}");
+        }
+    }
+
+    #[test]
+    fn test_eval_percent_reference() {
+        let vars = [("l", 50.0)];
+
+        assert_eq!(
+            eval("calc(l - 10%)", &vars, Some(100.0)).unwrap(),
+            Value::number(40.0)
+        );
+        assert_eq!(
+            eval("calc(l - 10%)", &vars, None).unwrap_err().kind(),
+            ErrorKind::IncompatibleUnits
+        );
+    }
+
+    #[test]
+    fn test_eval_errors() {
+        let cases = [
+            ("calc(1 + )", ErrorKind::InvalidCalc),
+            ("calc(1 + 2", ErrorKind::InvalidCalc),
+            ("calc(1 / 0)", ErrorKind::InvalidCalc),
+            ("calc(x)", ErrorKind::InvalidCalc),
+            ("foo(1)", ErrorKind::InvalidCalc),
+            ("clamp(1, 2)", ErrorKind::InvalidCalc),
+            ("calc(10deg + 10)", ErrorKind::IncompatibleUnits),
+            ("calc(10% * 10%)", ErrorKind::IncompatibleUnits),
+            ("calc(10px)", ErrorKind::IncompatibleUnits),
+            ("sqrt(4deg)", ErrorKind::IncompatibleUnits),
+            ("round(12.5%)", ErrorKind::InvalidCalc),
+        ];
+
+        for (s, kind) in cases {
+            assert_eq!(eval(s, &[], None).unwrap_err().kind(), kind, "{s}");
+        }
+    }
+}
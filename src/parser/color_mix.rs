@@ -0,0 +1,201 @@
+// CSS Color 5 `color-mix()`, e.g. `color-mix(in oklch, red 40%, blue)`.
+use crate::ColorComponent;
+use crate::Colorspace;
+use crate::Error;
+use crate::ErrorKind;
+use crate::HueInterpolation;
+use crate::Result;
+use crate::Solid;
+use crate::utils::{split_args, split_words};
+
+use super::ParseOptions;
+use super::parse_solid_with_options;
+
+/// Parses the arguments of `color-mix(in <space> [<hue-method> hue], <color> [<p>], <color> [<p>])`.
+///
+/// Percentages are normalized as defined by
+/// [CSS Color 5](https://www.w3.org/TR/css-color-5/#color-mix-percent-norm): when they add up
+/// to less than 100%, the result is made more transparent accordingly.
+///
+/// Components of the mixing space that are missing in both colors stay missing in the result.
+pub fn parse_color_mix(args: &str, original_s: &str, options: &ParseOptions) -> Result<Solid> {
+    let err = || Error::new(ErrorKind::InvalidColorMix, original_s);
+
+    let [method, c1, c2] = split_args(args)[..] else {
+        return Err(err());
+    };
+
+    let (space, hue) = parse_interpolation_method(method).ok_or_else(err)?;
+    let (c1, p1) = parse_mix_color(c1, options).ok_or_else(err)??;
+    let (c2, p2) = parse_mix_color(c2, options).ok_or_else(err)??;
+
+    let (p1, p2) = match (p1, p2) {
+        (None, None) => (0.5, 0.5),
+        (Some(p1), None) => (p1, 1.0 - p1),
+        (None, Some(p2)) => (1.0 - p2, p2),
+        (Some(p1), Some(p2)) => (p1, p2),
+    };
+
+    let sum = p1 + p2;
+    if sum <= 0.0 {
+        return Err(err());
+    }
+
+    let color = c1.interpolate(&c2, p2 / sum, space, hue);
+    let [r, g, b, a] = color.to_array();
+
+    let components = space.components().into_iter().flatten();
+    Ok(components
+        .chain([ColorComponent::Alpha])
+        .filter(|&component| color.is_missing(component))
+        .fold(Solid::new(r, g, b, a * sum.min(1.0)), Solid::with_missing))
+}
+
+/// Parses `in <space> [<hue-method> hue]`.
+pub fn parse_interpolation_method(s: &str) -> Option<(Colorspace, HueInterpolation)> {
+    match split_words(s)[..] {
+        ["in", space] => Some((
+            Colorspace::try_from(space).ok()?,
+            HueInterpolation::default(),
+        )),
+        ["in", space, method, "hue"] => {
+            let space = Colorspace::try_from(space).ok()?;
+            space.hue_index()?;
+            Some((space, HueInterpolation::try_from(method).ok()?))
+        }
+        _ => None,
+    }
+}
+
+/// Parses `<color> [<percentage>]`, the percentage may also come first.
+///
+/// Returns `None` when the percentage is invalid, and an error when the color is.
+fn parse_mix_color(s: &str, options: &ParseOptions) -> Option<Result<(Solid, Option<f32>)>> {
+    let words = split_words(s);
+
+    let (color, percent) = match words[..] {
+        [.., last] if last.ends_with('%') => (&s[..s.len() - last.len()], Some(last)),
+        [first, ..] if first.ends_with('%') => (&s[first.len()..], Some(first)),
+        _ => (s, None),
+    };
+
+    let percent = match percent {
+        Some(p) => {
+            let p = p.strip_suffix('%')?.parse::<f32>().ok()? / 100.0;
+            Some((0.0..=1.0).contains(&p).then_some(p)?)
+        }
+        None => None,
+    };
+
+    let color = color.trim();
+    if color.is_empty() {
+        return None;
+    }
+
+    Some(parse_solid_with_options(color, options).map(|c| (c, percent)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ColorComponent;
+    use crate::ErrorKind;
+    use crate::parser::parse_solid;
+
+    #[test]
+    fn test_color_mix() {
+        let cases = [
+            ("color-mix(in srgb, red, blue)", "#800080"),
+            ("color-mix(in srgb, red 25%, blue)", "#4000bf"),
+            ("color-mix(in srgb, 25% red, blue)", "#4000bf"),
+            ("color-mix(in srgb, red, blue 75%)", "#4000bf"),
+            ("color-mix(in srgb, red 12.5%, blue 37.5%)", "#4000bf80"),
+            ("color-mix(in srgb, red 25%, blue 25%)", "#80008080"),
+            ("color-mix(in srgb, red, transparent)", "#ff000080"),
+            ("color-mix(in hsl, red, blue)", "#ff00ff"),
+            ("color-mix(in hsl longer hue, red, blue)", "#00ff00"),
+            ("color-mix(in hsl, white, blue)", "#9f9fdf"),
+            ("color-mix(in oklab, white 100%, black)", "#ffffff"),
+            (
+                "color-mix(in srgb, color-mix(in srgb, red, blue), lime 0%)",
+                "#800080",
+            ),
+        ];
+
+        for (s, hex) in cases {
+            assert_eq!(parse_solid(s, None).unwrap().to_hex_string(), hex, "{s}");
+        }
+    }
+
+    #[test]
+    fn test_color_mix_missing() {
+        let cases = [
+            (
+                "color-mix(in srgb, rgb(none 0 0), rgb(none 10 0))",
+                ColorComponent::Red,
+                true,
+            ),
+            (
+                "color-mix(in srgb, rgb(none 0 0), rgb(10 10 0))",
+                ColorComponent::Red,
+                false,
+            ),
+            (
+                "color-mix(in hsl, hsl(none 50% 50%), hsl(none 100% 50%) 25%)",
+                ColorComponent::Hue,
+                true,
+            ),
+            (
+                "color-mix(in oklch, oklch(0.5 0.1 none), oklch(0.7 0.2 none))",
+                ColorComponent::Hue,
+                true,
+            ),
+            (
+                "color-mix(in oklch, oklch(0.5 0.1 none), oklch(0.7 0.2 120))",
+                ColorComponent::Hue,
+                false,
+            ),
+        ];
+
+        for (s, component, missing) in cases {
+            let color = parse_solid(s, None).unwrap();
+            assert_eq!(color.is_missing(component), missing, "{s}");
+        }
+
+        let color = parse_solid("color-mix(in srgb, rgb(none 0 0), rgb(none 10 0))", None);
+        assert_eq!(
+            color.unwrap().to_css_string(),
+            parse_solid("rgb(none 5 0)", None).unwrap().to_css_string()
+        );
+    }
+
+    #[test]
+    fn test_color_mix_oklch() {
+        let color = parse_solid("color-mix(in oklch, red, blue)", None).unwrap();
+        let [l, c, h] = color.to_colorspace(crate::Colorspace::Oklch);
+
+        assert!((l - 0.5399).abs() < 1e-3, "{l}");
+        assert!((c - 0.2854).abs() < 1e-3, "{c}");
+        assert!((h - 326.64).abs() < 0.1, "{h}");
+    }
+
+    #[test]
+    fn test_invalid_color_mix() {
+        let cases = [
+            "color-mix(srgb, red, blue)",
+            "color-mix(in srgb, red)",
+            "color-mix(in srgb longer hue, red, blue)",
+            "color-mix(in oklch sideways hue, red, blue)",
+            "color-mix(in srgb, red 0%, blue 0%)",
+            "color-mix(in srgb, red 120%, blue)",
+            "color-mix(in srgb, 10%, blue)",
+        ];
+
+        for s in cases {
+            assert_eq!(
+                parse_solid(s, None).unwrap_err().kind(),
+                ErrorKind::InvalidColorMix,
+                "{s}"
+            );
+        }
+    }
+}
@@ -0,0 +1,1064 @@
+// CSS Images gradients, e.g. `linear-gradient(45deg, red 10%, blue 40% 60%, green)`.
+use crate::Colorspace;
+use crate::Error;
+use crate::ErrorKind;
+use crate::HueInterpolation;
+use crate::Result;
+use crate::gradient::{
+    ConicGradient, Easing, Gradient, GradientCoordinates, GradientKind, GradientStop,
+    LengthPercentage, RadialGradient, RadialShape, RadialSize, StepPosition,
+};
+use crate::utils::{split_args, split_words};
+
+use super::ParseOptions;
+use super::calc::resolve_math_function;
+use super::color_mix::parse_interpolation_method;
+use super::parse_angle;
+use super::parse_solid_with_options;
+
+/// Splits `<fname>(<args>)` into its lowercased function name and its arguments, when `s` is
+/// one of the gradient functions (including the `repeating-` ones).
+pub fn gradient_function(s: &str) -> Option<(String, &str)> {
+    let s = s.trim().strip_suffix(')')?;
+    let (fname, args) = s.split_once('(')?;
+    let fname = fname.trim_end().to_ascii_lowercase();
+    let base = fname.strip_prefix("repeating-").unwrap_or(&fname);
+
+    let valid = match base {
+        "gradient" => base == fname,
+        "linear-gradient" | "radial-gradient" | "conic-gradient" => true,
+        _ => false,
+    };
+
+    valid.then_some((fname, args))
+}
+
+/// Parses the arguments of `linear-gradient([<angle> | to <side-or-corner>,] <color-stop-list>)`.
+///
+/// The direction defaults to `to bottom`.
+pub fn parse_linear_gradient(
+    args: &str,
+    original_s: &str,
+    options: &ParseOptions,
+) -> Result<Gradient> {
+    let err = || Error::new(ErrorKind::InvalidGradient, original_s);
+
+    let args = split_args(args);
+    let (method, first) = split_interpolation_method(args[0]).ok_or_else(err)?;
+    let to_bottom = || GradientCoordinates::from_css_direction("to bottom");
+
+    let (direction, stops) = match GradientCoordinates::from_css_direction(&first) {
+        Ok(direction) => (direction, &args[1..]),
+        Err(_) if first.starts_with("to ") => {
+            return Err(Error::new(ErrorKind::InvalidGradientCoordinates, args[0]));
+        }
+        Err(_) if method.is_some() && first.is_empty() => (to_bottom()?, &args[1..]),
+        Err(_) if method.is_some() => return Err(err()),
+        Err(_) => (to_bottom()?, &args[..]),
+    };
+
+    let stops = parse_color_stops(stops, parse_position, original_s, options)?;
+
+    Ok(with_interpolation_method(
+        Gradient::new(GradientKind::Linear(direction), stops),
+        method,
+    ))
+}
+
+/// Parses the arguments of
+/// `radial-gradient([<shape> || <size>] [at <position>], <color-stop-list>)`.
+///
+/// The shape defaults to `ellipse farthest-corner at center`.
+pub fn parse_radial_gradient(
+    args: &str,
+    original_s: &str,
+    options: &ParseOptions,
+) -> Result<Gradient> {
+    let err = || Error::new(ErrorKind::InvalidGradient, original_s);
+
+    let args = split_args(args);
+    let (method, first) = split_interpolation_method(args[0]).ok_or_else(err)?;
+
+    let (radial, stops) = match parse_radial_shape(&first) {
+        Some(radial) => (radial, &args[1..]),
+        None if method.is_some() && first.is_empty() => (RadialGradient::default(), &args[1..]),
+        None if method.is_some() || is_radial_shape(&first) => return Err(err()),
+        None => (RadialGradient::default(), &args[..]),
+    };
+
+    let stops = parse_color_stops(stops, parse_position, original_s, options)?;
+
+    Ok(with_interpolation_method(
+        Gradient::new(GradientKind::Radial(radial), stops),
+        method,
+    ))
+}
+
+/// Parses the arguments of `conic-gradient([from <angle>] [at <position>], <color-stop-list>)`,
+/// whose stop positions are angles or percentages of a full turn.
+pub fn parse_conic_gradient(
+    args: &str,
+    original_s: &str,
+    options: &ParseOptions,
+) -> Result<Gradient> {
+    let err = || Error::new(ErrorKind::InvalidGradient, original_s);
+
+    let args = split_args(args);
+    let (method, first) = split_interpolation_method(args[0]).ok_or_else(err)?;
+
+    let (conic, stops) = match parse_conic_shape(&first) {
+        Some(conic) => (conic, &args[1..]),
+        None if method.is_some() && first.is_empty() => (ConicGradient::default(), &args[1..]),
+        None if method.is_some() || first.starts_with("from") || first.starts_with("at") => {
+            return Err(err());
+        }
+        None => (ConicGradient::default(), &args[..]),
+    };
+
+    let stops = parse_color_stops(stops, parse_angular_position, original_s, options)?;
+
+    Ok(with_interpolation_method(
+        Gradient::new(GradientKind::Conic(conic), stops),
+        method,
+    ))
+}
+
+/// Splits the `in <space> [<hue-method> hue]` interpolation method out of the first argument of
+/// a gradient, which may come before or after the rest (e.g. `to right in oklch`), and returns
+/// the lowercased rest.
+///
+/// Returns `None` when the interpolation method is invalid.
+fn split_interpolation_method(s: &str) -> Option<(Option<(Colorspace, HueInterpolation)>, String)> {
+    let s = s.to_ascii_lowercase();
+    let words = split_words(&s);
+
+    let Some(i) = words.iter().position(|word| *word == "in") else {
+        return Some((None, s.clone()));
+    };
+    let len = if words.get(i + 3) == Some(&"hue") {
+        4
+    } else {
+        2
+    };
+    let method = parse_interpolation_method(&words.get(i..i + len)?.join(" "))?;
+    let rest = [&words[..i], &words[i + len..]].concat().join(" ");
+
+    Some((Some(method), rest))
+}
+
+fn with_interpolation_method(
+    gradient: Gradient,
+    method: Option<(Colorspace, HueInterpolation)>,
+) -> Gradient {
+    match method {
+        Some((interpolation_space, hue_interpolation)) => Gradient {
+            interpolation_space,
+            hue_interpolation,
+            ..gradient
+        },
+        None => gradient,
+    }
+}
+
+/// Parses `[from <angle>] [at <position>]`.
+fn parse_conic_shape(s: &str) -> Option<ConicGradient> {
+    let words = split_words(s);
+
+    let (angle, rest) = match words[..] {
+        ["from", angle, ref rest @ ..] => (parse_angle_with_unit(angle)?, rest),
+        _ => (0.0, &words[..]),
+    };
+    let center = match rest {
+        [] if words.is_empty() => return None,
+        [] => ConicGradient::default().center,
+        ["at", position @ ..] => parse_center(position)?,
+        _ => return None,
+    };
+
+    Some(ConicGradient { angle, center })
+}
+
+/// Parses an angular stop position, an `<angle>` or a `<percentage>` of a full turn.
+fn parse_angular_position(s: &str) -> Option<f32> {
+    parse_position(s).or_else(|| {
+        let s = resolve_math_function(s).ok()?;
+        parse_angle_with_unit(&s).map(|angle| angle / 360.0)
+    })
+}
+
+/// Parses an `<angle>` in degrees. Unlike hues, angles need a unit (except for `0`).
+fn parse_angle_with_unit(s: &str) -> Option<f32> {
+    match s.parse::<f32>() {
+        Ok(_) => (s == "0").then_some(0.0),
+        Err(_) => parse_angle(s),
+    }
+}
+
+/// Parses `[<shape> || <size>] [at <position>]`, where the size is a keyword, a length for
+/// circles, or two lengths or percentages for ellipses.
+fn parse_radial_shape(s: &str) -> Option<RadialGradient> {
+    let words = split_words(s);
+    let (shape_size, center) = match words.iter().position(|word| *word == "at") {
+        Some(i) => (&words[..i], parse_center(&words[i + 1..])?),
+        None if words.is_empty() => return None,
+        None => (&words[..], RadialGradient::default().center),
+    };
+
+    let mut shape = None;
+    let mut size = None;
+    let mut lengths = Vec::with_capacity(2);
+
+    for word in shape_size {
+        match *word {
+            "circle" if shape.is_none() => shape = Some(RadialShape::Circle),
+            "ellipse" if shape.is_none() => shape = Some(RadialShape::Ellipse),
+            "closest-side" if size.is_none() => size = Some(RadialSize::ClosestSide),
+            "farthest-side" if size.is_none() => size = Some(RadialSize::FarthestSide),
+            "closest-corner" if size.is_none() => size = Some(RadialSize::ClosestCorner),
+            "farthest-corner" if size.is_none() => size = Some(RadialSize::FarthestCorner),
+            _ => lengths.push(parse_length_percentage(word).filter(|r| r.resolve(1.0) >= 0.0)?),
+        }
+    }
+
+    let (shape, size) = match (shape, size, &lengths[..]) {
+        (shape, size, []) => (
+            shape.unwrap_or(RadialShape::Ellipse),
+            size.unwrap_or(RadialSize::FarthestCorner),
+        ),
+        (None | Some(RadialShape::Circle), None, [r @ LengthPercentage::Length(_)]) => {
+            (RadialShape::Circle, RadialSize::Explicit(*r, *r))
+        }
+        (None | Some(RadialShape::Ellipse), None, [rx, ry]) => {
+            (RadialShape::Ellipse, RadialSize::Explicit(*rx, *ry))
+        }
+        _ => return None,
+    };
+
+    Some(RadialGradient {
+        shape,
+        size,
+        center,
+    })
+}
+
+/// Whether the first argument of `radial-gradient()` is meant as its shape rather than a color.
+fn is_radial_shape(s: &str) -> bool {
+    split_words(s).first().is_some_and(|word| {
+        matches!(
+            *word,
+            "circle"
+                | "ellipse"
+                | "closest-side"
+                | "farthest-side"
+                | "closest-corner"
+                | "farthest-corner"
+                | "at"
+        ) || parse_length_percentage(word).is_some()
+    })
+}
+
+/// Parses a `<position>`, e.g. `left`, `30% 40%`, `bottom right` or `right 10% top 20%`.
+///
+/// Offsets from the right or bottom edge are only supported as percentages, since `100% - 10px`
+/// is not a single [`LengthPercentage`].
+fn parse_center(words: &[&str]) -> Option<[LengthPercentage; 2]> {
+    const CENTER: LengthPercentage = LengthPercentage::Percentage(0.5);
+
+    let keyword = |word: &str| match word {
+        "left" | "top" => Some(LengthPercentage::Percentage(0.0)),
+        "center" => Some(CENTER),
+        "right" | "bottom" => Some(LengthPercentage::Percentage(1.0)),
+        _ => None,
+    };
+    let is_vertical = |word: &str| matches!(word, "top" | "bottom");
+    let is_horizontal = |word: &str| matches!(word, "left" | "right");
+    let value = |word: &str| keyword(word).or_else(|| parse_length_percentage(word));
+
+    match *words {
+        [word] if is_vertical(word) => Some([CENTER, keyword(word)?]),
+        [word] => Some([value(word)?, CENTER]),
+        // Keywords may come in any order, e.g. `top left`.
+        [y, x] if keyword(x).is_some() && (is_vertical(y) || is_horizontal(x)) => {
+            (!is_vertical(x) && !is_horizontal(y)).then_some([keyword(x)?, keyword(y)?])
+        }
+        [x, y] if !is_vertical(x) && !is_horizontal(y) => Some([value(x)?, value(y)?]),
+        [_, _, _] | [_, _, _, _] => {
+            // Edge keywords followed by an optional offset from that edge, e.g. `right 10%`.
+            let mut sides = Vec::with_capacity(2);
+            let mut words = words;
+            while let [edge, rest @ ..] = words {
+                let offset = match rest.first() {
+                    Some(word) if *edge != "center" => parse_length_percentage(word),
+                    _ => None,
+                };
+                let value = match (*edge, offset) {
+                    (_, None) => keyword(edge)?,
+                    ("left" | "top", Some(offset)) => offset,
+                    ("right" | "bottom", Some(LengthPercentage::Percentage(p))) => {
+                        LengthPercentage::Percentage(1.0 - p)
+                    }
+                    _ => return None,
+                };
+                sides.push((*edge, value));
+                words = &rest[offset.is_some() as usize..];
+            }
+
+            match sides[..] {
+                [(y, y_value), (x, x_value)] if is_vertical(y) || is_horizontal(x) => {
+                    (!is_vertical(x) && !is_horizontal(y)).then_some([x_value, y_value])
+                }
+                [(x, x_value), (y, y_value)] if !is_vertical(x) && !is_horizontal(y) => {
+                    Some([x_value, y_value])
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Parses a `<length-percentage>`, with lengths in `px` (or `0`).
+fn parse_length_percentage(s: &str) -> Option<LengthPercentage> {
+    if let Some(p) = s.strip_suffix('%') {
+        return p
+            .parse::<f32>()
+            .ok()
+            .map(|p| LengthPercentage::Percentage(p / 100.0));
+    }
+
+    match s.strip_suffix("px") {
+        Some(px) => px.parse::<f32>().ok().map(LengthPercentage::Length),
+        None => (s == "0").then_some(LengthPercentage::Length(0.0)),
+    }
+}
+
+/// Parses a `<color-stop-list>`: color stops with 0, 1 or 2 positions, which may be separated
+/// by a color hint (a lone position) or an easing function, e.g. `red, 30%, blue` or
+/// `red, ease-in-out, blue`.
+///
+/// A stop with two positions is the same as two stops of the same color, one at each position.
+/// Positions are fractions of the gradient line, so lengths (e.g. `red 10px`) are rejected
+/// rather than resolved against a box.
+fn parse_color_stops(
+    args: &[&str],
+    parse_position: fn(&str) -> Option<f32>,
+    original_s: &str,
+    options: &ParseOptions,
+) -> Result<Vec<GradientStop>> {
+    let err = || Error::new(ErrorKind::InvalidGradient, original_s);
+
+    let mut colors = Vec::with_capacity(args.len());
+    let mut easings = Vec::with_capacity(args.len());
+    // The positions of the stops and color hints in order, with whether they are hints.
+    let mut positions = Vec::with_capacity(args.len());
+    let mut after_stop = false;
+
+    for (i, arg) in args.iter().enumerate() {
+        let words = split_words(arg);
+
+        // Transitions go between two color stops.
+        let transition = match words[..] {
+            [word] => parse_position(word)
+                .map(|position| (Easing::Hint(position), Some(position)))
+                .or_else(|| parse_easing(word).map(|easing| (easing, None))),
+            _ => None,
+        };
+        if let Some((easing, position)) = transition {
+            if !after_stop || i + 1 == args.len() {
+                return Err(err());
+            }
+            if let Some(last) = easings.last_mut() {
+                *last = easing;
+            }
+            if position.is_some() {
+                positions.push((position, true));
+            }
+            after_stop = false;
+            continue;
+        }
+
+        let count = words
+            .iter()
+            .rev()
+            .take(2)
+            .take_while(|word| parse_position(word).is_some())
+            .count();
+
+        let color = &words[..words.len() - count];
+        if let Some(LengthPercentage::Length(_)) =
+            color.last().and_then(|w| parse_length_percentage(w))
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidGradient,
+                format!("color stop positions must not be lengths: `{}`", arg.trim()),
+            ));
+        }
+        let color = color.join(" ");
+        if color.is_empty() {
+            return Err(err());
+        }
+        let color = parse_solid_with_options(&color, options)?;
+
+        match words[words.len() - count..] {
+            [] => positions.push((None, false)),
+            [p] => positions.push((parse_position(p), false)),
+            [p1, p2] => {
+                colors.push(color.clone());
+                easings.push(Easing::Linear);
+                positions.extend([(parse_position(p1), false), (parse_position(p2), false)]);
+            }
+            _ => unreachable!(),
+        }
+        colors.push(color);
+        easings.push(Easing::Linear);
+        after_stop = true;
+    }
+
+    if colors.len() < 2 {
+        return Err(err());
+    }
+
+    // Hints take part in the clamping of positions, but not in the spreading of the stops
+    // without positions.
+    let mut clamped = positions.iter().map(|&(p, _)| p).collect::<Vec<_>>();
+    clamp_positions(&mut clamped);
+    let stop_positions = fixup_positions(
+        &clamped
+            .iter()
+            .zip(&positions)
+            .filter(|(_, (_, hint))| !hint)
+            .map(|(&p, _)| p)
+            .collect::<Vec<_>>(),
+    );
+
+    let mut stops = colors
+        .into_iter()
+        .zip(stop_positions)
+        .zip(easings)
+        .map(|((color, position), easing)| GradientStop {
+            color,
+            position,
+            easing,
+        })
+        .collect::<Vec<_>>();
+
+    // Color hints are stored relative to their segment.
+    for i in 0..stops.len() - 1 {
+        if let Easing::Hint(hint) = stops[i].easing {
+            let (from, to) = (stops[i].position, stops[i + 1].position);
+            stops[i].easing = match to - from {
+                span if span > 0.0 => Easing::Hint(((hint.max(from) - from) / span).min(1.0)),
+                _ => Easing::Linear,
+            };
+        }
+    }
+
+    Ok(stops)
+}
+
+/// Parses an easing function between two color stops: `linear`, `ease`, `ease-in`, `ease-out`,
+/// `ease-in-out`, `cubic-bezier(x1, y1, x2, y2)`, `step-start`, `step-end` or
+/// `steps(<n>[, <step-position>])`.
+fn parse_easing(s: &str) -> Option<Easing> {
+    let s = s.to_ascii_lowercase();
+
+    match s.as_str() {
+        "linear" => return Some(Easing::Linear),
+        "ease" => return Some(Easing::EASE),
+        "ease-in" => return Some(Easing::EASE_IN),
+        "ease-out" => return Some(Easing::EASE_OUT),
+        "ease-in-out" => return Some(Easing::EASE_IN_OUT),
+        "step-start" => return Some(Easing::Steps(1, StepPosition::JumpStart)),
+        "step-end" => return Some(Easing::Steps(1, StepPosition::JumpEnd)),
+        _ => {}
+    }
+
+    let (fname, args) = s.strip_suffix(')')?.split_once('(')?;
+    let args = split_args(args);
+
+    match (fname.trim(), &args[..]) {
+        ("cubic-bezier", [x1, y1, x2, y2]) => {
+            let [x1, y1, x2, y2] = [x1, y1, x2, y2].map(|n| n.parse::<f32>().ok());
+            let (x1, y1, x2, y2) = (x1?, y1?, x2?, y2?);
+            let valid = (0.0..=1.0).contains(&x1) && (0.0..=1.0).contains(&x2);
+            valid.then_some(Easing::CubicBezier(x1, y1, x2, y2))
+        }
+        ("steps", [steps, rest @ ..]) => {
+            let steps = steps.parse::<u32>().ok()?;
+            let position = match rest {
+                [] => StepPosition::JumpEnd,
+                ["jump-start" | "start"] => StepPosition::JumpStart,
+                ["jump-end" | "end"] => StepPosition::JumpEnd,
+                ["jump-none"] => StepPosition::JumpNone,
+                ["jump-both"] => StepPosition::JumpBoth,
+                _ => return None,
+            };
+            let min = if position == StepPosition::JumpNone {
+                2
+            } else {
+                1
+            };
+            (steps >= min).then_some(Easing::Steps(steps, position))
+        }
+        _ => None,
+    }
+}
+
+/// Steps 1 and 2 of [`fixup_positions`].
+fn clamp_positions(positions: &mut [Option<f32>]) {
+    if let Some(first) = positions.first_mut() {
+        first.get_or_insert(0.0);
+    }
+    if let Some(last) = positions.last_mut() {
+        last.get_or_insert(1.0);
+    }
+
+    let mut max = f32::MIN;
+    for position in positions.iter_mut().flatten() {
+        max = max.max(*position);
+        *position = max;
+    }
+}
+
+/// Parses a `<percentage>` (or `0`) position into a fraction of the gradient line.
+fn parse_position(s: &str) -> Option<f32> {
+    let s = resolve_math_function(s).ok()?;
+
+    match s.strip_suffix('%') {
+        Some(p) => p.parse::<f32>().ok().map(|p| p / 100.0),
+        None => (s == "0").then_some(0.0),
+    }
+}
+
+/// Resolves missing and out-of-order positions, as defined by
+/// [CSS Images 3](https://www.w3.org/TR/css-images-3/#color-stop-fixup):
+///
+/// 1. The first and last stops default to 0% and 100%.
+/// 2. A position smaller than a previous one is raised to the largest previous position.
+/// 3. Runs of stops without positions are spread evenly between their neighbours.
+pub fn fixup_positions(positions: &[Option<f32>]) -> Vec<f32> {
+    let mut positions = positions.to_vec();
+    let len = positions.len();
+    clamp_positions(&mut positions);
+
+    let mut result = Vec::with_capacity(len);
+    let mut i = 0;
+    while i < len {
+        match positions[i] {
+            Some(position) => {
+                result.push(position);
+                i += 1;
+            }
+            None => {
+                // The first and last positions are set, so a run has neighbours on both sides.
+                let start = result[i - 1];
+                let next = (i..len).find(|&j| positions[j].is_some()).unwrap();
+                let end = positions[next].unwrap();
+                let steps = (next - i + 1) as f32;
+
+                for k in 1..=next - i {
+                    result.push(start + (end - start) * k as f32 / steps);
+                }
+                i = next;
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solid;
+    use crate::parser::parse_gradient;
+
+    fn stops(gradient: &Gradient) -> Vec<(String, f32)> {
+        gradient
+            .stops
+            .iter()
+            .map(|stop| (stop.color.to_hex_string(), stop.position))
+            .collect()
+    }
+
+    fn assert_coordinates(gradient: &Gradient, start: [f32; 2], end: [f32; 2]) {
+        let GradientKind::Linear(direction) = &gradient.kind else {
+            panic!("{gradient:?} is not linear");
+        };
+        for (a, b) in direction
+            .start
+            .iter()
+            .zip(start)
+            .chain(direction.end.iter().zip(end))
+        {
+            assert!((a - b).abs() < 1e-5, "{direction:?}");
+        }
+    }
+
+    #[test]
+    fn test_linear_gradient() {
+        let gradient =
+            parse_gradient("linear-gradient(45deg, red 10%, blue 40% 60%, green)", None).unwrap();
+        assert_coordinates(&gradient, [0.0, 1.0], [1.0, 0.0]);
+        assert_eq!(
+            stops(&gradient),
+            [
+                ("#ff0000".to_string(), 0.1),
+                ("#0000ff".to_string(), 0.4),
+                ("#0000ff".to_string(), 0.6),
+                ("#008000".to_string(), 1.0),
+            ]
+        );
+
+        let gradient = parse_gradient(
+            "Linear-Gradient(rgb(255 0 0 / 50%), oklch(70% 0.1 250) calc(25% * 2))",
+            None,
+        )
+        .unwrap();
+        assert_coordinates(&gradient, [0.5, 0.0], [0.5, 1.0]);
+        assert_eq!(gradient.stops[0].color, Solid::new(1.0, 0.0, 0.0, 0.5));
+        assert_eq!(gradient.stops[1].position, 0.5);
+    }
+
+    #[test]
+    fn test_linear_gradient_directions() {
+        let cases = [
+            ("to top", [0.5, 1.0], [0.5, 0.0]),
+            ("to right", [0.0, 0.5], [1.0, 0.5]),
+            ("to left", [1.0, 0.5], [0.0, 0.5]),
+            ("to bottom right", [0.0, 0.0], [1.0, 1.0]),
+            ("to right bottom", [0.0, 0.0], [1.0, 1.0]),
+            ("to top left", [1.0, 1.0], [0.0, 0.0]),
+            ("0.25turn", [0.0, 0.5], [1.0, 0.5]),
+            ("180deg", [0.5, 0.0], [0.5, 1.0]),
+            ("0", [0.5, 1.0], [0.5, 0.0]),
+        ];
+
+        for (direction, start, end) in cases {
+            let gradient =
+                parse_gradient(&format!("linear-gradient({direction}, red, blue)"), None).unwrap();
+            assert_coordinates(&gradient, start, end);
+        }
+    }
+
+    #[test]
+    fn test_radial_gradient() {
+        let px = LengthPercentage::Length;
+        let percent = LengthPercentage::Percentage;
+        let cases = [
+            (
+                "",
+                RadialShape::Ellipse,
+                RadialSize::FarthestCorner,
+                [percent(0.5); 2],
+            ),
+            (
+                "circle closest-side at 30% 40%, ",
+                RadialShape::Circle,
+                RadialSize::ClosestSide,
+                [percent(0.3), percent(0.4)],
+            ),
+            (
+                "farthest-side circle, ",
+                RadialShape::Circle,
+                RadialSize::FarthestSide,
+                [percent(0.5); 2],
+            ),
+            (
+                "closest-corner at top left, ",
+                RadialShape::Ellipse,
+                RadialSize::ClosestCorner,
+                [percent(0.0), percent(0.0)],
+            ),
+            (
+                "20px, ",
+                RadialShape::Circle,
+                RadialSize::Explicit(px(20.0), px(20.0)),
+                [percent(0.5); 2],
+            ),
+            (
+                "ellipse 50% 10px at right 8px, ",
+                RadialShape::Ellipse,
+                RadialSize::Explicit(percent(0.5), px(10.0)),
+                [percent(1.0), px(8.0)],
+            ),
+            (
+                "at bottom, ",
+                RadialShape::Ellipse,
+                RadialSize::FarthestCorner,
+                [percent(0.5), percent(1.0)],
+            ),
+            (
+                "at left 10% top 20%, ",
+                RadialShape::Ellipse,
+                RadialSize::FarthestCorner,
+                [percent(0.1), percent(0.2)],
+            ),
+            (
+                "at right 10% bottom 20%, ",
+                RadialShape::Ellipse,
+                RadialSize::FarthestCorner,
+                [percent(0.9), percent(0.8)],
+            ),
+            (
+                "ellipse 12px 50% at left 10% top 20%, ",
+                RadialShape::Ellipse,
+                RadialSize::Explicit(px(12.0), percent(0.5)),
+                [percent(0.1), percent(0.2)],
+            ),
+            (
+                "circle at top 5px left, ",
+                RadialShape::Circle,
+                RadialSize::FarthestCorner,
+                [percent(0.0), px(5.0)],
+            ),
+            (
+                "at center right 25%, ",
+                RadialShape::Ellipse,
+                RadialSize::FarthestCorner,
+                [percent(0.75), percent(0.5)],
+            ),
+        ];
+
+        for (shape, expected_shape, size, center) in cases {
+            let s = format!("radial-gradient({shape}red, blue 80%)");
+            let gradient = parse_gradient(&s, None).unwrap();
+            assert_eq!(
+                gradient.kind,
+                GradientKind::Radial(RadialGradient {
+                    shape: expected_shape,
+                    size,
+                    center,
+                }),
+                "{s}"
+            );
+            assert_eq!(stops(&gradient)[1], ("#0000ff".to_string(), 0.8));
+        }
+    }
+
+    #[test]
+    fn test_invalid_radial_gradient() {
+        let cases = [
+            "radial-gradient(circle 10% , red, blue)",
+            "radial-gradient(circle 10px 20px, red, blue)",
+            "radial-gradient(ellipse 10px, red, blue)",
+            "radial-gradient(circle ellipse, red, blue)",
+            "radial-gradient(circle at, red, blue)",
+            "radial-gradient(at left right, red, blue)",
+            "radial-gradient(at top 10%, red, blue)",
+            "radial-gradient(at left 10% right 20%, red, blue)",
+            "radial-gradient(at left 10% 20%, red, blue)",
+            "radial-gradient(at center 10% top, red, blue)",
+            "radial-gradient(at right 10px top, red, blue)",
+            "radial-gradient(-10px, red, blue)",
+            "radial-gradient(circle, red)",
+        ];
+
+        for s in cases {
+            assert_eq!(
+                parse_gradient(s, None).unwrap_err().kind(),
+                ErrorKind::InvalidGradient,
+                "{s}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_conic_gradient() {
+        let percent = LengthPercentage::Percentage;
+        let gradient = parse_gradient(
+            "conic-gradient(from 90deg at 25% 75%, red, blue 90deg, green 0.5turn 75%, red)",
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            gradient.kind,
+            GradientKind::Conic(ConicGradient {
+                angle: 90.0,
+                center: [percent(0.25), percent(0.75)],
+            })
+        );
+        assert_eq!(
+            stops(&gradient),
+            [
+                ("#ff0000".to_string(), 0.0),
+                ("#0000ff".to_string(), 0.25),
+                ("#008000".to_string(), 0.5),
+                ("#008000".to_string(), 0.75),
+                ("#ff0000".to_string(), 1.0),
+            ]
+        );
+
+        let cases = [
+            ("", ConicGradient::default()),
+            (
+                "from 0.5turn, ",
+                ConicGradient {
+                    angle: 180.0,
+                    ..Default::default()
+                },
+            ),
+            (
+                "at left, ",
+                ConicGradient {
+                    angle: 0.0,
+                    center: [percent(0.0), percent(0.5)],
+                },
+            ),
+        ];
+
+        for (shape, conic) in cases {
+            let s = format!("conic-gradient({shape}red, blue)");
+            let gradient = parse_gradient(&s, None).unwrap();
+            assert_eq!(gradient.kind, GradientKind::Conic(conic), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_conic_gradient() {
+        let cases = [
+            "conic-gradient(from 90, red, blue)",
+            "conic-gradient(from, red, blue)",
+            "conic-gradient(at 10% 10% from 90deg, red, blue)",
+            "conic-gradient(red, blue 50px)",
+            "conic-gradient(red)",
+        ];
+
+        for s in cases {
+            assert!(parse_gradient(s, None).is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_repeating_gradients() {
+        let cases = [
+            "repeating-linear-gradient(45deg, red, red 5%, blue 5%, blue 10%)",
+            "Repeating-Radial-Gradient(circle, red, blue 20%)",
+            "repeating-conic-gradient(red 0deg 10deg, blue 10deg 20deg)",
+        ];
+
+        for s in cases {
+            assert!(parse_gradient(s, None).unwrap().repeating, "{s}");
+        }
+
+        let gradient =
+            parse_gradient("repeating-linear-gradient(red 20%, blue 40%)", None).unwrap();
+        assert!((gradient.period() - 0.2).abs() < 1e-6);
+
+        for (t, expected) in [(0.2, 0.2), (0.3, 0.3), (0.5, 0.3), (0.1, 0.3), (-0.9, 0.3)] {
+            let wrapped = gradient.repeat_position(t);
+            assert!((wrapped - expected).abs() < 1e-5, "{t} => {wrapped}");
+        }
+
+        let gradient = parse_gradient("linear-gradient(red 20%, blue 40%)", None).unwrap();
+        assert!(!gradient.repeating);
+        assert_eq!(gradient.repeat_position(0.5), 0.5);
+
+        let gradient =
+            parse_gradient("repeating-linear-gradient(red 50%, blue 50%)", None).unwrap();
+        assert_eq!(gradient.period(), 0.0);
+        assert_eq!(gradient.repeat_position(0.9), 0.9);
+    }
+
+    #[test]
+    fn test_interpolation_method() {
+        let cases = [
+            (
+                "linear-gradient(in oklch longer hue, red, blue)",
+                Colorspace::Oklch,
+                HueInterpolation::Longer,
+            ),
+            (
+                "linear-gradient(to right in oklab, red, blue)",
+                Colorspace::Oklab,
+                HueInterpolation::Shorter,
+            ),
+            (
+                "linear-gradient(in hsl decreasing hue 45deg, red, blue)",
+                Colorspace::Hsl,
+                HueInterpolation::Decreasing,
+            ),
+            (
+                "radial-gradient(circle in display-p3, red, blue)",
+                Colorspace::DisplayP3,
+                HueInterpolation::Shorter,
+            ),
+            (
+                "conic-gradient(in lch increasing hue from 90deg, red, blue)",
+                Colorspace::Lch,
+                HueInterpolation::Increasing,
+            ),
+            (
+                "linear-gradient(red, blue)",
+                Colorspace::Srgb,
+                HueInterpolation::Shorter,
+            ),
+        ];
+
+        for (s, space, hue) in cases {
+            let gradient = parse_gradient(s, None).unwrap();
+            assert_eq!(gradient.interpolation_space, space, "{s}");
+            assert_eq!(gradient.hue_interpolation, hue, "{s}");
+        }
+
+        let gradient =
+            parse_gradient("linear-gradient(to right in oklch, red, blue)", None).unwrap();
+        assert_coordinates(&gradient, [0.0, 0.5], [1.0, 0.5]);
+
+        // Complementary colors keep their chroma in OKLCH, instead of going through grey.
+        let mid = gradient.interpolate(&gradient.stops[0].color, &gradient.stops[1].color, 0.5);
+        assert!(mid.to_oklch().c > 0.2);
+    }
+
+    #[test]
+    fn test_invalid_interpolation_method() {
+        let cases = [
+            "linear-gradient(in, red, blue)",
+            "linear-gradient(in nowhere, red, blue)",
+            "linear-gradient(in srgb longer hue, red, blue)",
+            "linear-gradient(in oklch sideways hue, red, blue)",
+            "linear-gradient(in oklch 45px, red, blue)",
+            "radial-gradient(in oklab square, red, blue)",
+            "conic-gradient(in oklab at, red, blue)",
+        ];
+
+        for s in cases {
+            assert!(parse_gradient(s, None).is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_color_hints_and_easing() {
+        let cases = [
+            ("linear-gradient(red, 25%, blue)", Easing::Hint(0.25)),
+            (
+                "linear-gradient(red 20%, 30%, blue 60%)",
+                Easing::Hint(0.25),
+            ),
+            ("linear-gradient(red 20%, 60%, blue 40%)", Easing::Hint(1.0)),
+            ("linear-gradient(red 50%, 20%, blue)", Easing::Hint(0.0)),
+            ("conic-gradient(red, 90deg, blue)", Easing::Hint(0.25)),
+            (
+                "linear-gradient(red, ease-in-out, blue)",
+                Easing::EASE_IN_OUT,
+            ),
+            (
+                "linear-gradient(red, Cubic-Bezier(0.1, -0.5, 0.9, 1.5), blue)",
+                Easing::CubicBezier(0.1, -0.5, 0.9, 1.5),
+            ),
+            (
+                "linear-gradient(red, steps(4), blue)",
+                Easing::Steps(4, StepPosition::JumpEnd),
+            ),
+            (
+                "linear-gradient(red, steps(4, jump-none), blue)",
+                Easing::Steps(4, StepPosition::JumpNone),
+            ),
+            (
+                "linear-gradient(red, step-start, blue)",
+                Easing::Steps(1, StepPosition::JumpStart),
+            ),
+        ];
+
+        for (s, easing) in cases {
+            let gradient = parse_gradient(s, None).unwrap();
+            assert_eq!(gradient.stops.len(), 2, "{s}");
+            assert_eq!(gradient.stops[0].easing, easing, "{s}");
+            assert_eq!(gradient.stops[1].easing, Easing::Linear, "{s}");
+        }
+
+        // Hints are not stops, so the stops around them are spread as if they were not there.
+        let gradient = parse_gradient("linear-gradient(red, 10%, lime, blue)", None).unwrap();
+        assert_eq!(stops(&gradient)[1], ("#00ff00".to_string(), 0.5));
+        assert_eq!(gradient.stops[0].easing, Easing::Hint(0.2));
+
+        // The color is halfway at the hint.
+        let hint = parse_gradient("linear-gradient(red, 25%, blue)", None).unwrap();
+        let linear = parse_gradient("linear-gradient(red, blue)", None).unwrap();
+        assert_eq!(hint.at(0.25), linear.at(0.5));
+
+        let steps = parse_gradient("linear-gradient(red, steps(2), blue)", None).unwrap();
+        assert_eq!(steps.at(0.25).to_hex_string(), "#ff0000");
+        assert_eq!(steps.at(0.75), linear.at(0.5));
+    }
+
+    #[test]
+    fn test_fixup_positions() {
+        let cases: [(&[Option<f32>], &[f32]); 5] = [
+            (&[None, None], &[0.0, 1.0]),
+            (
+                &[None, None, None, None, None],
+                &[0.0, 0.25, 0.5, 0.75, 1.0],
+            ),
+            (&[Some(0.2), None, Some(0.8)], &[0.2, 0.5, 0.8]),
+            (&[Some(0.5), Some(0.2), None], &[0.5, 0.5, 1.0]),
+            (
+                &[None, Some(0.8), None, Some(0.4), None],
+                &[0.0, 0.8, 0.8, 0.8, 1.0],
+            ),
+        ];
+
+        for (positions, expected) in cases {
+            assert_eq!(fixup_positions(positions), expected, "{positions:?}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_linear_gradient() {
+        let cases = [
+            ("linear-gradient(red)", ErrorKind::InvalidGradient),
+            ("linear-gradient(to top, red)", ErrorKind::InvalidGradient),
+            (
+                "linear-gradient(red, 50%, 60%, blue)",
+                ErrorKind::InvalidGradient,
+            ),
+            (
+                "linear-gradient(red, ease-in, 60%, blue)",
+                ErrorKind::InvalidGradient,
+            ),
+            (
+                "linear-gradient(red, blue, 50%)",
+                ErrorKind::InvalidGradient,
+            ),
+            (
+                "linear-gradient(ease, red, blue)",
+                ErrorKind::InvalidGradient,
+            ),
+            (
+                "linear-gradient(red, steps(0), blue)",
+                ErrorKind::InvalidFunction,
+            ),
+            (
+                "linear-gradient(red, cubic-bezier(2, 0, 1, 1), blue)",
+                ErrorKind::InvalidFunction,
+            ),
+            ("linear-gradient(10%, blue)", ErrorKind::InvalidGradient),
+            (
+                "linear-gradient(to middle, red, blue)",
+                ErrorKind::InvalidGradientCoordinates,
+            ),
+            ("linear-gradient(red, nocolor)", ErrorKind::InvalidUnknown),
+            ("gradient(nocolor, to right)", ErrorKind::InvalidGradient),
+            ("repeating-gradient(red, blue)", ErrorKind::InvalidGradient),
+            ("elliptic-gradient(red, blue)", ErrorKind::InvalidGradient),
+        ];
+
+        for (s, kind) in cases {
+            assert_eq!(parse_gradient(s, None).unwrap_err().kind(), kind, "{s}");
+        }
+
+        let cases = [
+            ("linear-gradient(0.5turn, red 10px, blue)", "`red 10px`"),
+            ("linear-gradient(red 10% 20px, blue)", "`red 10% 20px`"),
+            ("linear-gradient(red, 10px, blue)", "`10px`"),
+            ("radial-gradient(red, blue 5px 50%)", "`blue 5px 50%`"),
+        ];
+
+        for (s, stop) in cases {
+            let err = parse_gradient(s, None).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidGradient, "{s}");
+            assert!(err.message().contains(stop), "{s}: {err}");
+        }
+    }
+}
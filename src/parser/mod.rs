@@ -1,15 +1,27 @@
 use crate::Color;
+use crate::ColorComponent;
 use crate::ColorValue;
+use crate::Colorspace;
 use crate::ErrorKind;
 use crate::Solid;
+use crate::SystemColor;
 use crate::gradient::Gradient;
 use crate::gradient::GradientCoordinates;
 use crate::gradient::is_valid_direction;
-use crate::utils::get_accent;
+use crate::system_color::parser_system_color_provider;
+use crate::utils::split_args;
+use crate::utils::split_params;
 use crate::utils::strip_string;
 #[cfg(any(feature = "theme", feature = "theme_yml"))]
 use crate::{Theme, utils::PathClean};
 
+use adjust::parse_adjust;
+use calc::resolve_math_function;
+use color_mix::parse_color_mix;
+pub use gradient::fixup_positions;
+use gradient::{
+    gradient_function, parse_conic_gradient, parse_linear_gradient, parse_radial_gradient,
+};
 use named_colors::ACCENT_TRANSPARENT_PATTERN;
 use named_colors::HEX_PATTERN;
 use named_colors::HSLA_PATTERN;
@@ -17,6 +29,8 @@ use named_colors::RGBA_PATTERN;
 #[cfg(feature = "named-colors")]
 pub use named_colors::{NAMED_COLOR_PATTERN, NAMED_COLORS};
 use regex::Regex;
+use relative::is_relative;
+use relative::parse_relative;
 #[cfg(any(feature = "theme", feature = "theme_yml"))]
 use std::{
     env::current_dir,
@@ -25,11 +39,22 @@ use std::{
     sync::{LazyLock, RwLock},
     time::SystemTime,
 };
+use var::substitute_vars;
 
 pub use crate::Error;
 pub use crate::Result;
 
+mod adjust;
+mod calc;
+mod color_mix;
+mod gradient;
 mod named_colors;
+mod options;
+mod relative;
+mod var;
+
+pub use options::ColorScheme;
+pub use options::ParseOptions;
 
 #[cfg(any(feature = "theme", feature = "theme_yml"))]
 type ThemeCache = (String, Theme, SystemTime);
@@ -38,21 +63,54 @@ static THEME_CACHE: LazyLock<RwLock<Option<ThemeCache>>> = LazyLock::new(|| RwLo
 
 /// Parse CSS color string to solid (with optional theme)
 pub fn parse_solid(s: &str, file_path: Option<&str>) -> Result<Solid> {
+    parse_solid_with_options(
+        s,
+        &ParseOptions {
+            theme: file_path,
+            ..Default::default()
+        },
+    )
+}
+
+/// Parse CSS color string to solid, using the given parse options
+pub fn parse_solid_with_options(s: &str, options: &ParseOptions) -> Result<Solid> {
+    let s = substitute_vars(s, options)?;
+    let solid = parse_solid_value(&s, options)?;
+
+    if options.gamut_map {
+        return Ok(solid.gamut_map());
+    }
+
+    Ok(solid)
+}
+
+fn parse_solid_value(s: &str, options: &ParseOptions) -> Result<Solid> {
     let s = s.trim().to_ascii_lowercase();
 
     match s.as_str() {
         "transparent" => return Ok(Solid::new(0.0, 0.0, 0.0, 0.0)),
-        "accent" => return get_accent(true),
-        "accent_inactive" => return get_accent(false),
+        "currentcolor" => {
+            return options.current_color.cloned().ok_or_else(|| {
+                Error::new(ErrorKind::UnresolvedVariable, "currentcolor is not set")
+            });
+        }
+        "accent" => return parser_system_color_provider(&s)?.accent(options.color_scheme),
+        "accent_inactive" => {
+            return parser_system_color_provider(&s)?.inactive_accent(options.color_scheme);
+        }
         _ => {}
     }
 
     // Custom theme
     #[cfg(any(feature = "theme", feature = "theme_yml"))]
-    if let Some(file_path) = file_path {
-        if let Some(color) = parse_custom_theme(file_path)?.get_color(&s) {
-            return parse_solid(color.as_str(), None);
-        }
+    if let Some(file_path) = options.theme
+        && let Some(color) = parse_custom_theme(file_path)?.get_color(&s)
+    {
+        let options = ParseOptions {
+            theme: None,
+            ..*options
+        };
+        return parse_solid_with_options(color.as_str(), &options);
     }
 
     // Named colors
@@ -61,6 +119,11 @@ pub fn parse_solid(s: &str, file_path: Option<&str>) -> Result<Solid> {
         return Ok(Solid::from_rgba8(*r, *g, *b, 255));
     }
 
+    // System colors
+    if let Ok(color) = SystemColor::try_from(s.as_str()) {
+        return parser_system_color_provider(&s)?.system_color(color, options.color_scheme);
+    }
+
     // Hex format
     if let Some(s) = s.strip_prefix('#') {
         return parse_hex(s);
@@ -70,13 +133,38 @@ pub fn parse_solid(s: &str, file_path: Option<&str>) -> Result<Solid> {
 
     if let (Some(i), Some(s)) = (s.find('('), s.strip_suffix(')')) {
         let fname = &s[..i].trim_end();
-        let s = &s[i + 1..].replace([',', '/'], " ");
-        let params = s.split_whitespace().collect::<Vec<&str>>();
+        let args = &s[i + 1..];
+
+        if is_relative(args) {
+            return parse_relative(fname, args, original_s.as_str(), options);
+        }
+
+        // Math functions in channels are evaluated up front, e.g. `calc(120 + 45)` into `165`.
+        let resolved = match *fname {
+            "rgb" | "rgba" | "hsl" | "hsla" | "hwb" | "lab" | "lch" | "oklab" | "oklch"
+            | "color" => split_params(args)
+                .into_iter()
+                .map(resolve_math_function)
+                .collect::<Result<Vec<_>>>()?,
+            _ => Vec::new(),
+        };
+        let params = resolved.iter().map(AsRef::as_ref).collect::<Vec<&str>>();
 
         return match *fname {
             "rgb" | "rgba" => parse_rgb_or_rgba(params, original_s.as_str()),
             "hsl" | "hsla" => parse_hsl_or_hsla(params, original_s.as_str()),
-            _ => Err(Error::new(ErrorKind::InvalidFunction, s)),
+            "hwb" => parse_hwb(params, original_s.as_str()),
+            "lab" => parse_lab(params, original_s.as_str()),
+            "lch" => parse_lch(params, original_s.as_str()),
+            "oklab" => parse_oklab(params, original_s.as_str()),
+            "oklch" => parse_oklch(params, original_s.as_str()),
+            "color" => parse_color(params, original_s.as_str()),
+            "darken" | "lighten" | "saturate" | "desaturate" | "adjust-hue" | "spin"
+            | "grayscale" | "complement" | "invert" | "fade" | "fadein" | "fadeout" | "tint"
+            | "shade" | "mix" => parse_adjust(fname, args, original_s.as_str(), options),
+            "color-mix" => parse_color_mix(args, original_s.as_str(), options),
+            "light-dark" => parse_light_dark(args, original_s.as_str(), options),
+            _ => Err(Error::new(ErrorKind::InvalidFunction, original_s)),
         };
     }
 
@@ -89,6 +177,43 @@ pub fn parse_solid(s: &str, file_path: Option<&str>) -> Result<Solid> {
 }
 
 pub fn parse_gradient(s: &str, file_path: Option<&str>) -> Result<Gradient> {
+    parse_gradient_with_options(
+        s,
+        &ParseOptions {
+            theme: file_path,
+            ..Default::default()
+        },
+    )
+}
+
+pub fn parse_gradient_with_options(s: &str, options: &ParseOptions) -> Result<Gradient> {
+    let s = &*substitute_vars(s, options)?;
+
+    let Some((fname, args)) = gradient_function(s) else {
+        return Err(Error::new(ErrorKind::InvalidGradient, s));
+    };
+
+    let (repeating, fname) = match fname.strip_prefix("repeating-") {
+        Some(fname) => (true, fname),
+        None => (false, fname.as_str()),
+    };
+
+    let gradient = match fname {
+        "gradient" => parse_evenly_spaced_gradient(s.trim(), options),
+        "linear-gradient" => parse_linear_gradient(args, s, options),
+        "radial-gradient" => parse_radial_gradient(args, s, options),
+        "conic-gradient" => parse_conic_gradient(args, s, options),
+        _ => Err(Error::new(ErrorKind::InvalidGradient, s)),
+    }?;
+
+    Ok(Gradient {
+        repeating,
+        ..gradient
+    })
+}
+
+/// Parses `gradient(<color>, ..., [<direction>])`, whose colors are evenly spaced.
+fn parse_evenly_spaced_gradient(s: &str, options: &ParseOptions) -> Result<Gradient> {
     if !s.starts_with("gradient(") {
         return Err(Error::new(ErrorKind::InvalidGradient, s));
     }
@@ -104,39 +229,39 @@ pub fn parse_gradient(s: &str, file_path: Option<&str>) -> Result<Gradient> {
 
     #[cfg(any(feature = "theme", feature = "theme_yml"))]
     {
-        if let Some(file_path) = file_path {
-            if let Ok(theme_data) = parse_custom_theme(file_path) {
-                // Get the keys from the theme_data (assuming it's a map-like structure)
-                let theme_keys: Vec<_> = theme_data.colors();
-                let escaped_keys: Vec<String> = theme_keys
-                    .iter()
-                    .map(|key| key.replace('.', r"\."))
-                    .collect();
-
-                // Join the keys into a single pattern string separated by "|"
-                let theme_pattern_base = escaped_keys.join("|");
-                let theme_pattern = format!(r"\b(?:{})\b", theme_pattern_base);
-
-                // If `named-colors` is also enabled, include NAMED_COLOR_PATTERN as well
-                #[cfg(feature = "named-colors")]
-                {
-                    color_regex = Regex::new(
-                        format!(
-                            r"(?i){}|{}|{}",
-                            base_pattern, theme_pattern, NAMED_COLOR_PATTERN
-                        )
-                        .as_str(),
+        if let Some(file_path) = options.theme
+            && let Ok(theme_data) = parse_custom_theme(file_path)
+        {
+            // Get the keys from the theme_data (assuming it's a map-like structure)
+            let theme_keys: Vec<_> = theme_data.colors();
+            let escaped_keys: Vec<String> = theme_keys
+                .iter()
+                .map(|key| key.replace('.', r"\."))
+                .collect();
+
+            // Join the keys into a single pattern string separated by "|"
+            let theme_pattern_base = escaped_keys.join("|");
+            let theme_pattern = format!(r"\b(?:{})\b", theme_pattern_base);
+
+            // If `named-colors` is also enabled, include NAMED_COLOR_PATTERN as well
+            #[cfg(feature = "named-colors")]
+            {
+                color_regex = Regex::new(
+                    format!(
+                        r"(?i){}|{}|{}",
+                        base_pattern, theme_pattern, NAMED_COLOR_PATTERN
                     )
-                    .unwrap();
-                }
-
-                // If only `theme` is enabled (no `named-colors`), use just `theme_pattern`
-                #[cfg(not(feature = "named-colors"))]
-                {
-                    color_regex =
-                        Regex::new(format!(r"(?i){}|{}", base_pattern, theme_pattern).as_str())
-                            .unwrap();
-                }
+                    .as_str(),
+                )
+                .unwrap();
+            }
+
+            // If only `theme` is enabled (no `named-colors`), use just `theme_pattern`
+            #[cfg(not(feature = "named-colors"))]
+            {
+                color_regex =
+                    Regex::new(format!(r"(?i){}|{}", base_pattern, theme_pattern).as_str())
+                        .unwrap();
             }
         }
     }
@@ -153,9 +278,19 @@ pub fn parse_gradient(s: &str, file_path: Option<&str>) -> Result<Gradient> {
         .filter_map(|cap| cap.get(0).map(|m| m.as_str()))
         .collect::<Vec<&str>>();
 
-    let remaining_input = s
-        [s.rfind(color_matches.last().unwrap()).unwrap() + color_matches.last().unwrap().len()..]
-        .trim_start();
+    // The direction comes after the last color, without the closing parenthesis.
+    let remaining_input = match color_matches.last() {
+        Some(last) => binding[binding.rfind(last).unwrap() + last.len()..].trim_start(),
+        // Without colors (e.g. `gradient(to right)`), the gradient paints nothing.
+        None if binding
+            .split(',')
+            .map(str::trim)
+            .all(|s| s.is_empty() || is_valid_direction(s)) =>
+        {
+            binding.as_str()
+        }
+        None => return Err(Error::new(ErrorKind::InvalidGradient, s)),
+    };
 
     let remaining_input_arr = remaining_input
         .split(',')
@@ -173,19 +308,50 @@ pub fn parse_gradient(s: &str, file_path: Option<&str>) -> Result<Gradient> {
 
     let colors = color_matches
         .iter()
-        .filter_map(|&color| parse_solid(color, file_path).ok()) // Only keep Ok values
+        .filter_map(|&color| parse_solid_with_options(color, options).ok()) // Only keep Ok values
         .collect::<Vec<Solid>>();
 
     let direction = GradientCoordinates::try_from(direction.as_str())?;
 
-    Ok(Gradient { direction, colors })
+    Ok(Gradient::linear(direction, colors))
 }
 
 pub fn parse(s: &str, file_path: Option<&str>) -> Result<Color> {
-    if s.starts_with("gradient(") {
-        parse_gradient(s, file_path).map(|res| Color(ColorValue::Gradient(res)))
+    parse_with_options(
+        s,
+        &ParseOptions {
+            theme: file_path,
+            ..Default::default()
+        },
+    )
+}
+
+pub fn parse_with_options(s: &str, options: &ParseOptions) -> Result<Color> {
+    let s = &*substitute_vars(s, options)?;
+
+    if gradient_function(s).is_some() {
+        parse_gradient_with_options(s, options).map(|res| Color(ColorValue::Gradient(res)))
     } else {
-        parse_solid(s, file_path).map(|res| Color(ColorValue::Solid(res)))
+        parse_solid_with_options(s, options).map(|res| Color(ColorValue::Solid(res)))
+    }
+}
+
+/// Parses `light-dark(<light>, <dark>)`, picking the color for [`ParseOptions::color_scheme`].
+/// Both colors must be valid.
+fn parse_light_dark(args: &str, original_s: &str, options: &ParseOptions) -> Result<Solid> {
+    let [light, dark] = split_args(args)[..] else {
+        return Err(Error::new(ErrorKind::InvalidLightDark, original_s));
+    };
+    if light.is_empty() || dark.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidLightDark, original_s));
+    }
+
+    let light = parse_solid_with_options(light, options)?;
+    let dark = parse_solid_with_options(dark, options)?;
+
+    match options.color_scheme {
+        ColorScheme::Light => Ok(light),
+        ColorScheme::Dark => Ok(dark),
     }
 }
 
@@ -228,24 +394,21 @@ fn parse_rgb_or_rgba(params: Vec<&str>, original_s: &str) -> Result<Solid> {
         return Err(Error::new(ErrorKind::InvalidRgb, original_s));
     }
 
-    let r = parse_percent_or_255(params[0]);
-    let g = parse_percent_or_255(params[1]);
-    let b = parse_percent_or_255(params[2]);
-    let a = if params.len() == 4 {
-        parse_percent_or_float(params[3])
-    } else {
-        Some((1.0, true))
-    };
+    let r = parse_none_or_format(params[0], parse_percent_or_255);
+    let g = parse_none_or_format(params[1], parse_percent_or_255);
+    let b = parse_none_or_format(params[2], parse_percent_or_255);
+    let a = params.get(3).map_or(Some(1.0), |s| parse_alpha(s));
 
-    if let (Some((r, r_fmt)), Some((g, g_fmt)), Some((b, b_fmt)), Some((a, _))) = (r, g, b, a) {
-        if r_fmt == g_fmt && g_fmt == b_fmt {
-            return Ok(Solid::new(
-                r.clamp(0.0, 1.0),
-                g.clamp(0.0, 1.0),
-                b.clamp(0.0, 1.0),
-                a.clamp(0.0, 1.0),
-            ));
-        }
+    if let (Some((r, r_fmt)), Some((g, g_fmt)), Some((b, b_fmt)), Some(a)) = (r, g, b, a)
+        && is_same_format(&[r_fmt, g_fmt, b_fmt])
+    {
+        let solid = Solid::new(
+            r.clamp(0.0, 1.0),
+            g.clamp(0.0, 1.0),
+            b.clamp(0.0, 1.0),
+            a.clamp(0.0, 1.0),
+        );
+        return Ok(with_none_components(solid, Colorspace::Srgb, &params));
     }
 
     Err(Error::new(ErrorKind::InvalidRgb, original_s))
@@ -256,24 +419,191 @@ fn parse_hsl_or_hsla(params: Vec<&str>, original_s: &str) -> Result<Solid> {
         return Err(Error::new(ErrorKind::InvalidHsl, original_s));
     }
 
-    let h = parse_angle(params[0]);
-    let s = parse_percent_or_float(params[1]);
-    let l = parse_percent_or_float(params[2]);
-    let a = if params.len() == 4 {
-        parse_percent_or_float(params[3])
-    } else {
-        Some((1.0, true))
-    };
+    let h = parse_none_or(params[0], parse_angle);
+    let s = parse_none_or_format(params[1], parse_percent_or_float);
+    let l = parse_none_or_format(params[2], parse_percent_or_float);
+    let a = params.get(3).map_or(Some(1.0), |s| parse_alpha(s));
 
-    if let (Some(h), Some((s, s_fmt)), Some((l, l_fmt)), Some((a, _))) = (h, s, l, a) {
-        if s_fmt == l_fmt {
-            return Ok(Solid::from_normalized_hsla(h, s, l, a));
-        }
+    if let (Some(h), Some((s, s_fmt)), Some((l, l_fmt)), Some(a)) = (h, s, l, a)
+        && is_same_format(&[s_fmt, l_fmt])
+    {
+        let solid = Solid::from_normalized_hsla(h, s, l, a);
+        return Ok(with_none_components(solid, Colorspace::Hsl, &params));
     }
 
     Err(Error::new(ErrorKind::InvalidHsl, original_s))
 }
 
+fn parse_hwb(params: Vec<&str>, original_s: &str) -> Result<Solid> {
+    if params.len() != 3 && params.len() != 4 {
+        return Err(Error::new(ErrorKind::InvalidHwb, original_s));
+    }
+
+    let h = parse_none_or(params[0], parse_angle);
+    let w = parse_none_or(params[1], parse_percent_or_100);
+    let b = parse_none_or(params[2], parse_percent_or_100);
+    let a = params.get(3).map_or(Some(1.0), |s| parse_alpha(s));
+
+    if let (Some(h), Some(w), Some(b), Some(a)) = (h, w, b, a) {
+        let solid = Solid::from_hwba(h, w * 100.0, b * 100.0, a);
+        return Ok(with_none_components(solid, Colorspace::Hwb, &params));
+    }
+
+    Err(Error::new(ErrorKind::InvalidHwb, original_s))
+}
+
+fn parse_lab(params: Vec<&str>, original_s: &str) -> Result<Solid> {
+    if params.len() != 3 && params.len() != 4 {
+        return Err(Error::new(ErrorKind::InvalidLab, original_s));
+    }
+
+    let l = parse_none_or(params[0], |s| parse_number_or_percent(s, 100.0));
+    let a = parse_none_or(params[1], |s| parse_number_or_percent(s, 125.0));
+    let b = parse_none_or(params[2], |s| parse_number_or_percent(s, 125.0));
+    let alpha = params.get(3).map_or(Some(1.0), |s| parse_alpha(s));
+
+    if let (Some(l), Some(a), Some(b), Some(alpha)) = (l, a, b, alpha) {
+        let solid = Solid::from_lab(l.clamp(0.0, 100.0), a, b, alpha);
+        return Ok(with_none_components(solid, Colorspace::Lab, &params));
+    }
+
+    Err(Error::new(ErrorKind::InvalidLab, original_s))
+}
+
+fn parse_lch(params: Vec<&str>, original_s: &str) -> Result<Solid> {
+    if params.len() != 3 && params.len() != 4 {
+        return Err(Error::new(ErrorKind::InvalidLch, original_s));
+    }
+
+    let l = parse_none_or(params[0], |s| parse_number_or_percent(s, 100.0));
+    let c = parse_none_or(params[1], |s| parse_number_or_percent(s, 150.0));
+    let h = parse_none_or(params[2], parse_angle);
+    let alpha = params.get(3).map_or(Some(1.0), |s| parse_alpha(s));
+
+    if let (Some(l), Some(c), Some(h), Some(alpha)) = (l, c, h, alpha) {
+        let solid = Solid::from_lch(l.clamp(0.0, 100.0), c.max(0.0), h, alpha);
+        return Ok(with_none_components(solid, Colorspace::Lch, &params));
+    }
+
+    Err(Error::new(ErrorKind::InvalidLch, original_s))
+}
+
+fn parse_oklab(params: Vec<&str>, original_s: &str) -> Result<Solid> {
+    if params.len() != 3 && params.len() != 4 {
+        return Err(Error::new(ErrorKind::InvalidOklab, original_s));
+    }
+
+    let l = parse_none_or(params[0], |s| parse_number_or_percent(s, 1.0));
+    let a = parse_none_or(params[1], |s| parse_number_or_percent(s, 0.4));
+    let b = parse_none_or(params[2], |s| parse_number_or_percent(s, 0.4));
+    let alpha = params.get(3).map_or(Some(1.0), |s| parse_alpha(s));
+
+    if let (Some(l), Some(a), Some(b), Some(alpha)) = (l, a, b, alpha) {
+        let solid = Solid::from_oklab(l.clamp(0.0, 1.0), a, b, alpha);
+        return Ok(with_none_components(solid, Colorspace::Oklab, &params));
+    }
+
+    Err(Error::new(ErrorKind::InvalidOklab, original_s))
+}
+
+fn parse_oklch(params: Vec<&str>, original_s: &str) -> Result<Solid> {
+    if params.len() != 3 && params.len() != 4 {
+        return Err(Error::new(ErrorKind::InvalidOklch, original_s));
+    }
+
+    let l = parse_none_or(params[0], |s| parse_number_or_percent(s, 1.0));
+    let c = parse_none_or(params[1], |s| parse_number_or_percent(s, 0.4));
+    let h = parse_none_or(params[2], parse_angle);
+    let alpha = params.get(3).map_or(Some(1.0), |s| parse_alpha(s));
+
+    if let (Some(l), Some(c), Some(h), Some(alpha)) = (l, c, h, alpha) {
+        let solid = Solid::from_oklch(l.clamp(0.0, 1.0), c.max(0.0), h, alpha);
+        return Ok(with_none_components(solid, Colorspace::Oklch, &params));
+    }
+
+    Err(Error::new(ErrorKind::InvalidOklch, original_s))
+}
+
+fn parse_color(params: Vec<&str>, original_s: &str) -> Result<Solid> {
+    if params.len() != 4 && params.len() != 5 {
+        return Err(Error::new(ErrorKind::InvalidColor, original_s));
+    }
+
+    let space = Colorspace::try_from(params[0])?;
+    if !space.is_predefined() {
+        return Err(Error::new(ErrorKind::InvalidColor, original_s));
+    }
+
+    let c1 = parse_none_or(params[1], |s| parse_number_or_percent(s, 1.0));
+    let c2 = parse_none_or(params[2], |s| parse_number_or_percent(s, 1.0));
+    let c3 = parse_none_or(params[3], |s| parse_number_or_percent(s, 1.0));
+    let alpha = params.get(4).map_or(Some(1.0), |s| parse_alpha(s));
+
+    if let (Some(c1), Some(c2), Some(c3), Some(alpha)) = (c1, c2, c3, alpha) {
+        let solid = Solid::from_colorspace(space, [c1, c2, c3], alpha);
+        return Ok(with_none_components(solid, space, &params[1..]));
+    }
+
+    Err(Error::new(ErrorKind::InvalidColor, original_s))
+}
+
+/// Parses a channel that may also be the `none` keyword, which stands for a missing component
+/// (zero, see [`Solid::is_missing`]).
+fn parse_none_or(s: &str, parse: impl Fn(&str) -> Option<f32>) -> Option<f32> {
+    match s {
+        "none" => Some(0.0),
+        _ => parse(s),
+    }
+}
+
+/// Like [`parse_none_or`], for channels that must share the same format (number or percentage)
+/// with their siblings; `none` has no format.
+fn parse_none_or_format(
+    s: &str,
+    parse: impl Fn(&str) -> Option<(f32, bool)>,
+) -> Option<(f32, Option<bool>)> {
+    match s {
+        "none" => Some((0.0, None)),
+        _ => parse(s).map(|(t, fmt)| (t, Some(fmt))),
+    }
+}
+
+/// Whether all the channels that are not `none` have the same format.
+fn is_same_format(formats: &[Option<bool>]) -> bool {
+    let mut formats = formats.iter().flatten();
+    let first = formats.next();
+
+    formats.all(|fmt| Some(fmt) == first)
+}
+
+/// Marks the channels given as `none` as missing. `params` are the three channels of `space`,
+/// optionally followed by alpha.
+fn with_none_components(solid: Solid, space: Colorspace, params: &[&str]) -> Solid {
+    let components = space
+        .components()
+        .into_iter()
+        .chain([Some(ColorComponent::Alpha)]);
+
+    params
+        .iter()
+        .zip(components)
+        .filter(|(s, _)| **s == "none")
+        .filter_map(|(_, component)| component)
+        .fold(solid, Solid::with_missing)
+}
+
+fn parse_alpha(s: &str) -> Option<f32> {
+    parse_none_or(s, |s| parse_percent_or_float(s).map(|(t, _)| t))
+}
+
+/// Parses a number, or a percentage of `reference` (e.g. `50%` of 125 is 62.5).
+fn parse_number_or_percent(s: &str, reference: f32) -> Option<f32> {
+    match s.strip_suffix('%') {
+        Some(num) => num.parse().ok().map(|t: f32| t / 100.0 * reference),
+        None => s.parse().ok(),
+    }
+}
+
 fn parse_percent_or_float(s: &str) -> Option<(f32, bool)> {
     match s.strip_suffix('%') {
         Some(num) => num.parse().ok().map(|t: f32| (t / 100.0, true)),
@@ -288,6 +618,13 @@ fn parse_percent_or_255(s: &str) -> Option<(f32, bool)> {
     }
 }
 
+fn parse_percent_or_100(s: &str) -> Option<f32> {
+    match s.strip_suffix('%') {
+        Some(num) => num.parse().ok().map(|t: f32| t / 100.0),
+        None => s.parse().ok().map(|t: f32| t / 100.0),
+    }
+}
+
 fn parse_angle(s: &str) -> Option<f32> {
     if let Some(s) = s.strip_suffix("deg") {
         return s.parse().ok();
@@ -323,13 +660,12 @@ fn parse_custom_theme(file_path: &str) -> Result<Theme> {
         .map_err(|e| Error::new(ErrorKind::InvalidUnknown, format!("{:?}", e)))?;
 
     // If the cache exists and the path has not changed, check the modification timestamp.
-    if let Some((cached_path, theme_data, cached_time)) = THEME_CACHE.read().unwrap().as_ref() {
-        if cached_path == &full_path.to_string_lossy().into_owned()
-            && current_modified != *cached_time
-        {
-            // If path matches and the file has been modified, reload and update the cache.
-            return Ok(theme_data.clone());
-        }
+    if let Some((cached_path, theme_data, cached_time)) = THEME_CACHE.read().unwrap().as_ref()
+        && cached_path == &full_path.to_string_lossy().into_owned()
+        && current_modified != *cached_time
+    {
+        // If path matches and the file has been modified, reload and update the cache.
+        return Ok(theme_data.clone());
     }
 
     // If no cache or path changed, reload the theme.
@@ -357,3 +693,329 @@ fn reload_and_cache_theme(file_path: PathBuf) -> Result<Theme> {
 
     Ok(theme)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HueInterpolation;
+
+    #[test]
+    fn test_parse_hwb() {
+        let cases = [
+            ("hwb(120 0% 0%)", "#00ff00"),
+            ("hwb(120deg 0 0)", "#00ff00"),
+            ("hwb(0.5turn 0% 0%)", "#00ffff"),
+            ("hwb(194 0% 0% / .5)", "#00c3ff80"),
+            ("hwb(none 50% 50%)", "#808080"),
+            ("hwb(0 60% 60%)", "#808080"),
+        ];
+
+        for (s, hex) in cases {
+            assert_eq!(parse_solid(s, None).unwrap().to_hex_string(), hex, "{s}");
+        }
+
+        let solid = parse_solid("hwb(0 20% 20% / none)", None).unwrap();
+        assert_eq!(solid.to_array()[3], 0.0);
+
+        let err = parse_solid("hwb(120 0%)", None).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidHwb);
+    }
+
+    #[test]
+    fn test_parse_lab_and_lch() {
+        let cases = [
+            ("lab(52.2345% 40.1645 59.9971)", "#c65d06"),
+            ("lab(52.2345 32.1316% 47.9977%)", "#c65d06"),
+            ("lch(52.2345% 72.2 56.2)", "#c65d06"),
+            ("lch(52.2345% 48.1333% 56.2deg)", "#c65d06"),
+            ("lab(100% 0 0)", "#ffffff"),
+            ("lch(0% none none)", "#000000"),
+            ("lab(29.2345% 39.3825 20.0664 / 50%)", "#7d232980"),
+        ];
+
+        for (s, hex) in cases {
+            assert_eq!(parse_solid(s, None).unwrap().to_hex_string(), hex, "{s}");
+        }
+
+        let err = parse_solid("lab(50% 10)", None).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidLab);
+
+        let err = parse_solid("lch(50% 10 red)", None).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidLch);
+    }
+
+    #[test]
+    fn test_lab_round_trip() {
+        let solid = Solid::from_html("#89b4fa").unwrap();
+        let lab = solid.to_lab();
+        let lch = solid.to_lch();
+
+        assert_eq!(
+            Solid::from_lab(lab.l, lab.a, lab.b, lab.alpha).to_hex_string(),
+            "#89b4fa"
+        );
+        assert_eq!(
+            Solid::from_lch(lch.l, lch.c, lch.h, lch.alpha).to_hex_string(),
+            "#89b4fa"
+        );
+    }
+
+    #[test]
+    fn test_parse_oklab_and_oklch() {
+        let cases = [
+            ("oklab(62.8% 0.2249 0.1258)", "#ff0000"),
+            ("oklab(0.628 56.225% 31.45%)", "#ff0000"),
+            ("oklch(62.8% 0.2577 29.23)", "#ff0000"),
+            ("oklch(0.628 64.43% 29.23deg / 0.5)", "#ff000080"),
+            ("oklch(100% 0 none)", "#ffffff"),
+        ];
+
+        for (s, hex) in cases {
+            assert_eq!(parse_solid(s, None).unwrap().to_hex_string(), hex, "{s}");
+        }
+
+        let err = parse_solid("oklch(50% 0.1)", None).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidOklch);
+    }
+
+    #[test]
+    fn test_interpolate_oklch() {
+        let red = Solid::from_html("red").unwrap();
+        let blue = Solid::from_html("blue").unwrap();
+        let grey = Solid::from_html("grey").unwrap();
+
+        let shorter = red.interpolate_oklch(&blue, 0.5, HueInterpolation::Shorter);
+        let longer = red.interpolate_oklch(&blue, 0.5, HueInterpolation::Longer);
+        assert!(shorter.to_oklch().h > 264.0 || shorter.to_oklch().h < 29.0);
+        assert!((29.0..264.0).contains(&longer.to_oklch().h));
+
+        // The achromatic grey takes the hue of the other color.
+        let mid = grey.interpolate_oklch(&blue, 0.5, HueInterpolation::Shorter);
+        assert!((mid.to_oklch().h - blue.to_oklch().h).abs() < 0.5);
+
+        let mid = red.interpolate_oklab(&blue, 0.5).to_oklab();
+        let (lab_1, lab_2) = (red.to_oklab(), blue.to_oklab());
+        assert!((mid.l - (lab_1.l + lab_2.l) / 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_parse_color() {
+        let cases = [
+            ("color(srgb 1 0.5 0)", "#ff8000"),
+            ("color(srgb 100% 50% 0% / 50%)", "#ff800080"),
+            ("color(srgb-linear 1 0.2159 0)", "#ff8000"),
+            ("color(display-p3 0.9177 0.2003 0.1387)", "#ff0000"),
+            ("color(a98-rgb 0.8591 0 0)", "#ff0000"),
+            ("color(prophoto-rgb 0.7022 0.2757 0.1036)", "#ff0000"),
+            ("color(rec2020 0.7919 0.2310 0.0738)", "#ff0000"),
+            ("color(xyz-d65 0.4124 0.2126 0.0193)", "#ff0000"),
+            ("color(xyz 0.9505 1 1.089)", "#ffffff"),
+            ("color(xyz-d50 0.9643 1 0.8251)", "#ffffff"),
+        ];
+
+        for (s, hex) in cases {
+            assert_eq!(parse_solid(s, None).unwrap().to_hex_string(), hex, "{s}");
+        }
+
+        let err = parse_solid("color(display-p4 1 0 0)", None).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidColorspace);
+
+        let err = parse_solid("color(display-p3 1 0)", None).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidColor);
+    }
+
+    #[test]
+    fn test_wide_gamut_round_trip() {
+        let p3 = parse_solid("color(display-p3 0 1 0)", None).unwrap();
+        let [r, g, b] = p3.to_colorspace(Colorspace::DisplayP3);
+
+        assert!(p3.to_array()[0] < 0.0 && p3.to_array()[1] > 1.0);
+        assert!(r.abs() < 1e-4 && (g - 1.0).abs() < 1e-4 && b.abs() < 1e-4);
+        assert_eq!(p3.clamp().to_hex_string(), "#00ff00");
+    }
+
+    #[test]
+    fn test_gamut_map() {
+        let p3 = parse_solid("color(display-p3 0 1 0)", None).unwrap();
+        assert!(!p3.is_in_gamut());
+
+        let mapped = p3.gamut_map();
+        let (origin, lch) = (p3.to_oklch(), mapped.to_oklch());
+        assert!(mapped.is_in_gamut());
+        let clipped = p3.clamp().to_oklch();
+        assert!((origin.h - lch.h).abs() < (origin.h - clipped.h).abs());
+        assert!(lch.c < origin.c);
+
+        let options = ParseOptions {
+            gamut_map: true,
+            ..Default::default()
+        };
+        let solid = parse_solid_with_options("oklch(70% 0.4 30)", &options).unwrap();
+        assert!(solid.is_in_gamut());
+
+        let white = parse_solid_with_options("lab(100% 50 50)", &options).unwrap();
+        assert_eq!(white.to_hex_string(), "#ffffff");
+
+        let red = Solid::from_html("red").unwrap();
+        assert_eq!(red.gamut_map(), red);
+    }
+
+    #[test]
+    fn test_hwba_round_trip() {
+        let solid = Solid::from_html("#89b4fa").unwrap();
+        let hwba = solid.to_hwba();
+
+        assert_eq!(
+            Solid::from_hwba(hwba.h, hwba.w, hwba.b, hwba.a).to_hex_string(),
+            "#89b4fa"
+        );
+    }
+
+    #[test]
+    fn test_math_functions_in_channels() {
+        let cases = [
+            ("hsl(calc(120 + 45) 80% 50%)", "#1ae6b3"),
+            ("rgb(calc(255 / 2) min(255, 300) 0)", "#80ff00"),
+            (
+                "rgb(calc(100% - 50%), 0%, clamp(0%, 120%, 100%))",
+                "#8000ff",
+            ),
+            ("rgb(0 255 0/calc(1 / 2))", "#00ff0080"),
+            ("hsl(calc(0.5turn - 60deg) 100% 50%)", "#00ff00"),
+            ("hwb(mod(-240, 360) 0% 0%)", "#00ff00"),
+            ("color(srgb round(0.6) 0 0)", "#ff0000"),
+        ];
+
+        for (s, hex) in cases {
+            assert_eq!(parse_solid(s, None).unwrap().to_hex_string(), hex, "{s}");
+        }
+
+        assert_eq!(
+            parse_solid("oklch(0.7 0.1 calc(90deg * 2))", None).unwrap(),
+            parse_solid("oklch(0.7 0.1 180)", None).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_math_functions_in_channels() {
+        let cases = [
+            ("rgb(calc(10deg) 0 0)", ErrorKind::InvalidRgb),
+            ("hsl(calc(1 + ) 0% 0%)", ErrorKind::InvalidCalc),
+            ("rgb(calc(50% + 10) 0 0)", ErrorKind::IncompatibleUnits),
+            ("lab(50 max(10, 5deg) 0)", ErrorKind::IncompatibleUnits),
+        ];
+
+        for (s, kind) in cases {
+            assert_eq!(parse_solid(s, None).unwrap_err().kind(), kind, "{s}");
+        }
+    }
+
+    #[test]
+    fn test_none_components() {
+        let cases = [
+            ("rgb(none 255 0)", ColorComponent::Red),
+            ("rgb(255 0 0 / none)", ColorComponent::Alpha),
+            ("hsl(none 0% 50%)", ColorComponent::Hue),
+            ("hsl(120 none 50%)", ColorComponent::Colorfulness),
+            ("hwb(none 20% 20%)", ColorComponent::Hue),
+            ("lab(50 none 20)", ColorComponent::OpponentA),
+            ("oklch(0.5 0.1 none)", ColorComponent::Hue),
+            ("color(xyz 0.2 none 0.2)", ColorComponent::Green),
+            ("hsl(from red none s l)", ColorComponent::Hue),
+        ];
+
+        for (s, component) in cases {
+            let color = parse_solid(s, None).unwrap();
+            assert!(color.is_missing(component), "{s}");
+        }
+
+        assert!(
+            !parse_solid("hsl(0 0% 50%)", None)
+                .unwrap()
+                .is_missing(ColorComponent::Hue)
+        );
+        assert_eq!(
+            parse_solid("rgb(none 50% 100%)", None)
+                .unwrap()
+                .to_hex_string(),
+            "#0080ff"
+        );
+    }
+
+    #[test]
+    fn test_none_components_to_css_string() {
+        let cases = [
+            ("rgb(none 255 0)", "color(srgb none 1 0)"),
+            ("rgb(255 0 0 / none)", "color(srgb 1 0 0 / none)"),
+            ("hsl(none 0% 50%)", "hsl(none 0% 50%)"),
+            ("hsl(none 100% 50% / 0.5)", "hsl(none 100% 50% / 0.5)"),
+            ("hsl(120 none 50%)", "hsl(0 none 50%)"),
+            ("hwb(none 20% 20%)", "hwb(none 20% 20%)"),
+            ("lab(50 none 20)", "lab(50 none 20)"),
+            ("oklch(0.5 0.1 none)", "oklch(0.5 0.1 none)"),
+            ("color(xyz 0.2 none 0.2)", "color(xyz-d65 0.2 none 0.2)"),
+        ];
+
+        for (s, css) in cases {
+            let color = parse_solid(s, None).unwrap();
+            assert_eq!(color.to_css_string(), css, "{s}");
+            assert_eq!(parse_solid(css, None).unwrap(), color, "{s}");
+        }
+    }
+
+    #[test]
+    fn test_interpolate_missing_components() {
+        let grey = parse_solid("hsl(none 0% 50%)", None).unwrap();
+        let blue = parse_solid("hsl(240 100% 50%)", None).unwrap();
+
+        // The missing hue takes blue's hue instead of going from red (0) to blue.
+        let mid = grey.interpolate(&blue, 0.5, Colorspace::Hsl, HueInterpolation::Shorter);
+        assert!((mid.to_hsla().h - 240.0).abs() < 0.01);
+
+        let red = parse_solid("rgb(none 0 0)", None).unwrap();
+        let yellow = parse_solid("rgb(255 255 0)", None).unwrap();
+        assert_eq!(red.interpolate_rgb(&yellow, 0.5).to_hex_string(), "#ff8000");
+
+        // Missing in both colors stays missing.
+        let a = parse_solid("oklch(0.4 0.1 none)", None).unwrap();
+        let b = parse_solid("oklch(0.8 0.1 none)", None).unwrap();
+        let mid = a.interpolate_oklch(&b, 0.5, HueInterpolation::Shorter);
+        assert!(mid.is_missing(ColorComponent::Hue));
+        assert!((mid.to_oklch().l - 0.6).abs() < 1e-3);
+
+        // Analogous components carry over to other color spaces.
+        let lab = parse_solid("lab(50 none 0)", None).unwrap();
+        let other = parse_solid("oklab(0.5 0.1 0)", None).unwrap();
+        let mid = lab.interpolate_oklab(&other, 0.5);
+        assert!((mid.to_oklab().a - 0.1).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_light_dark() {
+        let s = "light-dark(rgb(255, 255, 255), darken(#ffffff, 100%))";
+        let dark = ParseOptions::with_color_scheme(ColorScheme::Dark);
+
+        assert_eq!(parse_solid(s, None).unwrap().to_hex_string(), "#ffffff");
+        assert_eq!(
+            parse_solid_with_options(s, &dark).unwrap().to_hex_string(),
+            "#000000"
+        );
+        assert_eq!(
+            parse_solid_with_options("color-mix(in srgb, light-dark(red, blue), blue)", &dark)
+                .unwrap()
+                .to_hex_string(),
+            "#0000ff"
+        );
+
+        let cases = [
+            ("light-dark(red)", ErrorKind::InvalidLightDark),
+            ("light-dark(red, )", ErrorKind::InvalidLightDark),
+            ("light-dark(red, blue, lime)", ErrorKind::InvalidLightDark),
+            ("light-dark(red, rgb(0))", ErrorKind::InvalidRgb),
+        ];
+
+        for (s, kind) in cases {
+            assert_eq!(parse_solid(s, None).unwrap_err().kind(), kind, "{s}");
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use crate::Solid;
+
+/// Options that control how CSS color strings are parsed.
+///
+/// ```
+/// use colorparser_css::{ParseOptions, Solid};
+///
+/// let options = ParseOptions {
+///     gamut_map: true,
+///     ..Default::default()
+/// };
+///
+/// let solid = Solid::from_html_with_options("color(display-p3 0 1 0)", &options).unwrap();
+/// assert!(solid.is_in_gamut());
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ParseOptions<'a> {
+    /// Path of a custom theme file, whose keys can be used in place of colors.
+    pub theme: Option<&'a str>,
+    /// Bring colors outside the sRGB gamut (e.g. from `oklch()` or `color(display-p3 ...)`) into
+    /// the gamut with [`Solid::gamut_map`](crate::Solid::gamut_map), instead of keeping their
+    /// out-of-range channels.
+    pub gamut_map: bool,
+    /// The color scheme `light-dark(<light>, <dark>)` resolves against.
+    pub color_scheme: ColorScheme,
+    /// The custom properties `var(--name)` resolves against, keyed by their name including the
+    /// leading `--`.
+    pub variables: Option<&'a HashMap<String, String>>,
+    /// The color `currentColor` resolves to.
+    pub current_color: Option<&'a Solid>,
+}
+
+impl<'a> ParseOptions<'a> {
+    /// Create options that resolve theme keys from the theme file at `path`.
+    pub fn with_theme(path: &'a str) -> Self {
+        Self {
+            theme: Some(path),
+            ..Default::default()
+        }
+    }
+
+    /// Create options that resolve `light-dark()` against the given color scheme.
+    pub fn with_color_scheme(color_scheme: ColorScheme) -> Self {
+        Self {
+            color_scheme,
+            ..Default::default()
+        }
+    }
+
+    /// Create options that resolve `var()` against the given custom properties.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use colorparser_css::{ParseOptions, Solid};
+    ///
+    /// let variables = HashMap::from([("--accent".to_string(), "#89b4fa".to_string())]);
+    /// let options = ParseOptions::with_variables(&variables);
+    ///
+    /// let solid = Solid::from_html_with_options("var(--border, var(--accent))", &options).unwrap();
+    /// assert_eq!(solid.to_hex_string(), "#89b4fa");
+    /// ```
+    pub fn with_variables(variables: &'a HashMap<String, String>) -> Self {
+        Self {
+            variables: Some(variables),
+            ..Default::default()
+        }
+    }
+}
+
+/// The color scheme used to resolve the CSS `light-dark()` function.
+///
+/// ```
+/// use colorparser_css::{ColorScheme, Solid};
+///
+/// let s = "light-dark(#eff1f5, #1e1e2e)";
+///
+/// assert_eq!(Solid::from_html(s).unwrap().to_hex_string(), "#eff1f5");
+/// assert_eq!(
+///     Solid::from_html_with_color_scheme(s, ColorScheme::Dark).unwrap().to_hex_string(),
+///     "#1e1e2e"
+/// );
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorScheme {
+    /// Use the first color of `light-dark()` (the default).
+    #[default]
+    Light,
+    /// Use the second color of `light-dark()`.
+    Dark,
+}
@@ -0,0 +1,239 @@
+// CSS Color 5 relative colors, e.g. `hsl(from #89b4fa h s calc(l - 10%))`.
+use crate::Colorspace;
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+use crate::Solid;
+use crate::utils::{split_words, top_level_separators};
+
+use super::ParseOptions;
+use super::calc::eval;
+use super::parse_solid_value;
+use super::with_none_components;
+
+/// The reference of hue channels, which take angles instead of percentages.
+const HUE: Option<f32> = None;
+
+/// Parses the arguments of a relative color, `<fname>(from <color> <c1> <c2> <c3> [/ <alpha>])`
+/// (`color(from <color> <space> <c1> <c2> <c3> [/ <alpha>])` for `color()`).
+///
+/// The origin color accepts every syntax `parse_solid` does, including theme keys and `accent`.
+/// Each channel may use the channel keywords of the function (e.g. `r`, `g`, `b` and `alpha`
+/// for `rgb()`), which hold the components of the origin color, inside `calc()` expressions.
+pub fn parse_relative(
+    fname: &str,
+    args: &str,
+    original_s: &str,
+    options: &ParseOptions,
+) -> Result<Solid> {
+    let err = || Error::new(error_kind(fname), original_s);
+
+    let (channels, alpha) = split_alpha(args);
+    let words = split_words(channels);
+    let ["from", origin, rest @ ..] = &words[..] else {
+        return Err(err());
+    };
+
+    // The origin color is used as is, gamut mapping only applies to the final color.
+    let origin = parse_solid_value(origin, options)?;
+
+    let (space, rest) = match (fname, rest) {
+        ("color", [space, rest @ ..]) => {
+            let space = Colorspace::try_from(*space)?;
+            if !space.is_predefined() {
+                return Err(err());
+            }
+            (space, rest)
+        }
+        _ => (function_space(fname).ok_or_else(err)?, rest),
+    };
+
+    let [c1, c2, c3] = rest else {
+        return Err(err());
+    };
+    let params = [*c1, *c2, *c3, alpha.unwrap_or_default()];
+
+    let (names, references, scale) = channels_of(space);
+    let c = origin.to_colorspace(space).map(|t| t * scale);
+    let origin_alpha = origin.to_array()[3];
+    let vars = [
+        (names[0], c[0]),
+        (names[1], c[1]),
+        (names[2], c[2]),
+        ("alpha", origin_alpha),
+    ];
+
+    let channel = |s: &str, reference: Option<f32>| match s {
+        "none" => Ok(0.0),
+        _ => eval(s, &vars, reference)?
+            .resolve(reference)
+            .ok_or_else(err),
+    };
+
+    let c1 = channel(c1, references[0])?;
+    let c2 = channel(c2, references[1])?;
+    let c3 = channel(c3, references[2])?;
+    let alpha = match alpha {
+        Some(s) => channel(s, Some(1.0))?,
+        None => origin_alpha,
+    };
+
+    let c = match space {
+        Colorspace::Srgb if fname != "color" => [c1, c2, c3].map(|t| (t / scale).clamp(0.0, 1.0)),
+        Colorspace::Hsl | Colorspace::Hwb => [c1, c2.clamp(0.0, 100.0), c3.clamp(0.0, 100.0)],
+        Colorspace::Lab => [c1.clamp(0.0, 100.0), c2, c3],
+        Colorspace::Lch => [c1.clamp(0.0, 100.0), c2.max(0.0), c3],
+        Colorspace::Oklab => [c1.clamp(0.0, 1.0), c2, c3],
+        Colorspace::Oklch => [c1.clamp(0.0, 1.0), c2.max(0.0), c3],
+        _ => [c1, c2, c3],
+    };
+
+    let solid = Solid::from_colorspace(space, c, alpha.clamp(0.0, 1.0));
+
+    Ok(with_none_components(solid, space, &params))
+}
+
+/// Whether the arguments of a color function use the relative color syntax.
+pub fn is_relative(args: &str) -> bool {
+    args.trim_start()
+        .strip_prefix("from")
+        .is_some_and(|s| s.starts_with(char::is_whitespace))
+}
+
+/// Splits `<channels> / <alpha>` on the top-level slash.
+fn split_alpha(s: &str) -> (&str, Option<&str>) {
+    match top_level_separators(s, |c| c == '/').next() {
+        Some((i, _)) => (&s[..i], Some(s[i + 1..].trim())),
+        None => (s, None),
+    }
+}
+
+fn function_space(fname: &str) -> Option<Colorspace> {
+    match fname {
+        "rgb" | "rgba" => Some(Colorspace::Srgb),
+        "hsl" | "hsla" => Some(Colorspace::Hsl),
+        "hwb" => Some(Colorspace::Hwb),
+        "lab" => Some(Colorspace::Lab),
+        "lch" => Some(Colorspace::Lch),
+        "oklab" => Some(Colorspace::Oklab),
+        "oklch" => Some(Colorspace::Oklch),
+        _ => None,
+    }
+}
+
+/// Returns the channel keywords of a color space, the references their percentages resolve
+/// against, and the factor between the keyword values and the `Colorspace` components.
+fn channels_of(space: Colorspace) -> ([&'static str; 3], [Option<f32>; 3], f32) {
+    match space {
+        Colorspace::Srgb => (["r", "g", "b"], [Some(255.0); 3], 255.0),
+        Colorspace::XyzD50 | Colorspace::XyzD65 => (["x", "y", "z"], [Some(1.0); 3], 1.0),
+        Colorspace::Hsl => (["h", "s", "l"], [HUE, Some(100.0), Some(100.0)], 1.0),
+        Colorspace::Hwb => (["h", "w", "b"], [HUE, Some(100.0), Some(100.0)], 1.0),
+        Colorspace::Lab => (
+            ["l", "a", "b"],
+            [Some(100.0), Some(125.0), Some(125.0)],
+            1.0,
+        ),
+        Colorspace::Lch => (["l", "c", "h"], [Some(100.0), Some(150.0), HUE], 1.0),
+        Colorspace::Oklab => (["l", "a", "b"], [Some(1.0), Some(0.4), Some(0.4)], 1.0),
+        Colorspace::Oklch => (["l", "c", "h"], [Some(1.0), Some(0.4), HUE], 1.0),
+        _ => (["r", "g", "b"], [Some(1.0); 3], 1.0),
+    }
+}
+
+fn error_kind(fname: &str) -> ErrorKind {
+    match fname {
+        "rgb" | "rgba" => ErrorKind::InvalidRgb,
+        "hsl" | "hsla" => ErrorKind::InvalidHsl,
+        "hwb" => ErrorKind::InvalidHwb,
+        "lab" => ErrorKind::InvalidLab,
+        "lch" => ErrorKind::InvalidLch,
+        "oklab" => ErrorKind::InvalidOklab,
+        "oklch" => ErrorKind::InvalidOklch,
+        "color" => ErrorKind::InvalidColor,
+        _ => ErrorKind::InvalidFunction,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_solid;
+
+    #[test]
+    fn test_relative_colors() {
+        let cases = [
+            ("rgb(from #89b4fa r g b / 50%)", "#89b4fa80"),
+            ("rgb(from #89b4fa b g r)", "#fab489"),
+            ("rgb(from red calc(r / 2) g 255)", "#8000ff"),
+            (
+                "rgba(from rgb(0 0 0 / 50%) 255 g b / calc(alpha * 2))",
+                "#ff0000",
+            ),
+            ("hsl(from red calc(h + 120) s l)", "#00ff00"),
+            ("hsl(from red h s calc(l - 10%))", "#cc0000"),
+            ("hsl(from red h s calc(l + 100))", "#ffffff"),
+            ("hwb(from red h 100% b)", "#ffffff"),
+            ("lab(from white 0 a b)", "#000000"),
+            ("oklch(from red l c calc(h + 180))", "#00a9db"),
+            ("oklab(from #89b4fa l 0 0)", "#b3b3b3"),
+            ("color(from red srgb g r b)", "#00ff00"),
+            ("rgb(from hsl(from red h s 25%) r g b)", "#800000"),
+        ];
+
+        for (s, hex) in cases {
+            assert_eq!(parse_solid(s, None).unwrap().to_hex_string(), hex, "{s}");
+        }
+    }
+
+    #[test]
+    fn test_relative_round_trip() {
+        for s in [
+            "lch(from #89b4fa l c h)",
+            "oklch(from #89b4fa l c h / alpha)",
+            "color(from #89b4fa display-p3 r g b)",
+            "color(from #89b4fa xyz x y z)",
+        ] {
+            assert_eq!(
+                parse_solid(s, None).unwrap().to_hex_string(),
+                "#89b4fa",
+                "{s}"
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "theme")]
+    fn test_relative_theme_key() {
+        let color = parse_solid(
+            "hsl(from blue h s calc(l - 10%) / 50%)",
+            Some("examples/theme.json"),
+        )
+        .unwrap();
+        let base = Solid::from_html("#89b4fa").unwrap().to_hsla();
+
+        assert_eq!(color.to_hsla().l.round(), (base.l - 10.0).round());
+        assert_eq!(color.to_array()[3], 0.5);
+    }
+
+    #[test]
+    fn test_invalid_relative_colors() {
+        let cases = [
+            ("rgb(from #89b4fa r g)", ErrorKind::InvalidRgb),
+            ("rgb(from #89b4fa r g h)", ErrorKind::InvalidCalc),
+            ("hsl(from red 10% s l)", ErrorKind::InvalidHsl),
+            ("oklch(from red l c calc(h +))", ErrorKind::InvalidCalc),
+            (
+                "oklch(from red l c calc(h + 10%))",
+                ErrorKind::IncompatibleUnits,
+            ),
+            ("rgb(from red 10deg g b)", ErrorKind::InvalidRgb),
+            ("color(from red oklch l c h)", ErrorKind::InvalidColor),
+            ("rgb(from nocolor r g b)", ErrorKind::InvalidUnknown),
+        ];
+
+        for (s, kind) in cases {
+            assert_eq!(parse_solid(s, None).unwrap_err().kind(), kind, "{s}");
+        }
+    }
+}
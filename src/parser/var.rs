@@ -0,0 +1,212 @@
+// Substitution of CSS custom properties, e.g. `var(--accent, #89b4fa)`.
+use std::borrow::Cow;
+
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+use crate::utils::top_level_separators;
+
+use super::ParseOptions;
+
+/// Replaces every `var(--name[, <fallback>])` of `s` by the value of `--name` in
+/// [`ParseOptions::variables`], or by its fallback when the variable is not defined.
+///
+/// Values and fallbacks may themselves use `var()`. Returns
+/// [`ErrorKind::UnresolvedVariable`] for a variable that is neither defined nor has a fallback,
+/// and for variables that reference themselves.
+pub fn substitute_vars<'a>(s: &'a str, options: &ParseOptions) -> Result<Cow<'a, str>> {
+    if find_var(s).is_none() {
+        return Ok(Cow::Borrowed(s));
+    }
+
+    substitute(s, options, &mut Vec::new()).map(Cow::Owned)
+}
+
+/// `stack` holds the names of the variables being substituted, to detect cycles.
+fn substitute(s: &str, options: &ParseOptions, stack: &mut Vec<String>) -> Result<String> {
+    let mut result = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = find_var(rest) {
+        result.push_str(&rest[..start]);
+
+        let args_start = start + "var(".len();
+        let len = closing_paren(&rest[args_start..])
+            .ok_or_else(|| Error::new(ErrorKind::UnresolvedVariable, s))?;
+        let args = &rest[args_start..args_start + len];
+        rest = &rest[args_start + len + 1..];
+
+        let (name, fallback) = match args.split_once(',') {
+            Some((name, fallback)) => (name.trim(), Some(fallback.trim())),
+            None => (args.trim(), None),
+        };
+
+        if !name.starts_with("--") {
+            return Err(Error::new(ErrorKind::UnresolvedVariable, s));
+        }
+        if stack.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+            return Err(Error::new(
+                ErrorKind::UnresolvedVariable,
+                format!("cyclic reference to {name}"),
+            ));
+        }
+
+        let value = match (lookup(options, name), fallback) {
+            (Some(value), _) => {
+                stack.push(name.to_string());
+                let value = substitute(value, options, stack)?;
+                stack.pop();
+                value
+            }
+            (None, Some(fallback)) => substitute(fallback, options, stack)?,
+            (None, None) => {
+                return Err(Error::new(
+                    ErrorKind::UnresolvedVariable,
+                    format!("{name} is not defined"),
+                ));
+            }
+        };
+
+        result.push_str(value.trim());
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Color strings are lowercased before parsing, so names are compared case-insensitively when
+/// there is no exact match.
+fn lookup<'a>(options: &ParseOptions<'a>, name: &str) -> Option<&'a str> {
+    let variables = options.variables?;
+
+    variables
+        .get(name)
+        .or_else(|| {
+            variables
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value)
+        })
+        .map(String::as_str)
+}
+
+/// Byte offset of the first `var(`, ignoring case.
+fn find_var(s: &str) -> Option<usize> {
+    s.as_bytes()
+        .windows(4)
+        .position(|w| w.eq_ignore_ascii_case(b"var("))
+}
+
+/// Byte offset of the parenthesis closing the one just before `s`.
+fn closing_paren(s: &str) -> Option<usize> {
+    top_level_separators(s, |c| c == ')').next().map(|(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::Solid;
+    use crate::parser::{parse_gradient_with_options, parse_solid_with_options};
+
+    fn variables() -> HashMap<String, String> {
+        HashMap::from([
+            ("--accent".to_string(), "#89b4fa".to_string()),
+            ("--Surface".to_string(), "rgb(30, 30, 46)".to_string()),
+            ("--border".to_string(), "var(--accent)".to_string()),
+            ("--alpha".to_string(), "50%".to_string()),
+            ("--a".to_string(), "var(--b)".to_string()),
+            ("--b".to_string(), "var(--a, red)".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_var() {
+        let variables = variables();
+        let options = ParseOptions::with_variables(&variables);
+
+        let cases = [
+            ("var(--accent)", "#89b4fa"),
+            ("VAR(--surface)", "#1e1e2e"),
+            ("var(--border)", "#89b4fa"),
+            ("var(--missing, var(--accent))", "#89b4fa"),
+            ("var(--missing, var(--other, rgb(255, 0, 0)))", "#ff0000"),
+            ("rgb(from var(--accent) r g b / var(--alpha))", "#89b4fa80"),
+            ("darken(var(--missing, #ffffff), 100%)", "#000000"),
+        ];
+
+        for (s, hex) in cases {
+            assert_eq!(
+                parse_solid_with_options(s, &options)
+                    .unwrap()
+                    .to_hex_string(),
+                hex,
+                "{s}"
+            );
+        }
+
+        let gradient =
+            parse_gradient_with_options("gradient(var(--accent), #000000, to right)", &options)
+                .unwrap();
+        assert_eq!(
+            gradient.stops[0].color,
+            Solid::from_html("#89b4fa").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_current_color() {
+        let current = Solid::from_html("#f38ba8").unwrap();
+        let options = ParseOptions {
+            current_color: Some(&current),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            parse_solid_with_options("currentColor", &options).unwrap(),
+            current
+        );
+        assert_eq!(
+            parse_solid_with_options("color-mix(in srgb, currentcolor, black 0%)", &options)
+                .unwrap()
+                .to_hex_string(),
+            "#f38ba8"
+        );
+
+        let gradient =
+            parse_gradient_with_options("gradient(currentColor, #000000, to right)", &options)
+                .unwrap();
+        assert_eq!(gradient.stops[0].color, current);
+
+        assert_eq!(
+            parse_solid_with_options("currentColor", &ParseOptions::default())
+                .unwrap_err()
+                .kind(),
+            ErrorKind::UnresolvedVariable
+        );
+    }
+
+    #[test]
+    fn test_unresolved_var() {
+        let variables = variables();
+        let options = ParseOptions::with_variables(&variables);
+
+        let cases = [
+            "var(--missing)",
+            "var(--a)",
+            "var(--b)",
+            "var(accent)",
+            "var(--accent",
+            "rgb(var(--missing) 0 0)",
+        ];
+
+        for s in cases {
+            assert_eq!(
+                parse_solid_with_options(s, &options).unwrap_err().kind(),
+                ErrorKind::UnresolvedVariable,
+                "{s}"
+            );
+        }
+    }
+}
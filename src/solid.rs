@@ -3,24 +3,58 @@ use std::fmt;
 use std::ops::Mul;
 use std::str::FromStr;
 
+#[cfg(feature = "schema")]
+use schema_jsonrs::{JsonSchema, Schema, SchemaGenerator, json_schema};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Visitor};
+#[cfg(feature = "schema")]
+use std::borrow::Cow;
 
+use crate::ColorComponent;
+use crate::Colorspace;
 use crate::Error;
 use crate::Hsla;
+use crate::HueInterpolation;
+use crate::Hwba;
+use crate::Lab;
+use crate::Lch;
 use crate::NormalizedHsla;
 use crate::NormalizedRgba;
+use crate::Oklab;
+use crate::Oklch;
 use crate::Result as SolidResult;
 use crate::Rgba;
 use crate::Rgba16;
 #[cfg(feature = "named-colors")]
 use crate::parser::NAMED_COLORS;
-use crate::parser::parse_solid;
-use crate::utils::{clamp0_1, hsl_to_rgb, normalize_angle, rgb_to_hsl};
+use crate::parser::{ColorScheme, ParseOptions, parse_solid, parse_solid_with_options};
+use crate::utils::{
+    clamp0_1, colorspace_to_rgb, hsl_to_rgb, hwb_to_rgb, lab_to_lch, lab_to_rgb, lch_to_lab,
+    linear_to_srgb, normalize_angle, oklab_to_rgb, rgb_to_colorspace, rgb_to_hsl, rgb_to_hwb,
+    rgb_to_lab, rgb_to_oklab, srgb_to_linear,
+};
+
+/// Channel tolerance used by [`Solid::is_in_gamut`] to absorb floating point error.
+const GAMUT_EPSILON: f32 = 1e-5;
+
+/// Just noticeable difference in OKLab used by [`Solid::gamut_map`].
+const GAMUT_JND: f32 = 0.02;
+
+/// Precision of the chroma search in [`Solid::gamut_map`].
+const GAMUT_MAP_EPSILON: f32 = 0.0001;
+
+/// OKLCH chroma below which a color is treated as achromatic, so its hue is powerless.
+const ACHROMATIC_CHROMA: f32 = 1e-4;
 
-#[cfg_attr(feature = "schema", derive(schema_jsonrs::JsonSchema))]
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
-pub struct Solid(f32, f32, f32, f32);
+pub struct Solid(
+    f32,
+    f32,
+    f32,
+    f32,
+    // Bit set of the missing (`none`) components, see `ColorComponent`.
+    u16,
+);
 
 impl Solid {
     /// Arguments:
@@ -30,7 +64,22 @@ impl Solid {
     /// * `b`: Blue value [0..1]
     /// * `a`: Alpha value [0..1]
     pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
-        Self(r, g, b, a)
+        Self(r, g, b, a, 0)
+    }
+
+    /// Whether the given component is missing, i.e. was given as the CSS `none` keyword.
+    ///
+    /// A missing component behaves as zero, except when interpolating: it then takes the value
+    /// of the other color, e.g. `hsl(none 0% 50%)` blends with blue without going through red.
+    pub fn is_missing(&self, component: ColorComponent) -> bool {
+        self.4 & component_bit(component) != 0
+    }
+
+    /// Marks the given component as missing, as the CSS `none` keyword does. The channel values
+    /// are left as they are.
+    pub fn with_missing(mut self, component: ColorComponent) -> Self {
+        self.4 |= component_bit(component);
+        self
     }
 
     pub fn to_array(&self) -> [f32; 4] {
@@ -64,15 +113,90 @@ impl Solid {
         }
     }
 
+    /// Clip every channel to the range [0..1], bringing out-of-gamut colors into sRGB.
     pub fn clamp(&self) -> Self {
         Self(
             self.0.clamp(0.0, 1.0),
             self.1.clamp(0.0, 1.0),
             self.2.clamp(0.0, 1.0),
             self.3.clamp(0.0, 1.0),
+            self.4,
         )
     }
 
+    /// Whether the red, green and blue channels lie inside the sRGB gamut.
+    pub fn is_in_gamut(&self) -> bool {
+        [self.0, self.1, self.2]
+            .iter()
+            .all(|c| (-GAMUT_EPSILON..=1.0 + GAMUT_EPSILON).contains(c))
+    }
+
+    /// Bring an out-of-gamut color into sRGB using the
+    /// [CSS Color 4 gamut mapping](https://www.w3.org/TR/css-color-4/#gamut-mapping) algorithm.
+    ///
+    /// Unlike [`Solid::clamp`], which clips every channel and can shift the hue noticeably, this
+    /// reduces the OKLCH chroma while keeping lightness and hue, until clipping the result is no
+    /// longer perceptible.
+    pub fn gamut_map(&self) -> Self {
+        if self.is_in_gamut() {
+            return self.clamp();
+        }
+
+        let origin = self.to_oklch();
+        let alpha = clamp0_1(self.3);
+
+        if origin.l >= 1.0 {
+            return Self::new(1.0, 1.0, 1.0, alpha);
+        }
+        if origin.l <= 0.0 {
+            return Self::new(0.0, 0.0, 0.0, alpha);
+        }
+
+        let delta_e_ok = |a: &Solid, b: &Solid| {
+            let (a, b) = (a.to_oklab(), b.to_oklab());
+            ((a.l - b.l).powi(2) + (a.a - b.a).powi(2) + (a.b - b.b).powi(2)).sqrt()
+        };
+
+        let mut current = self.clone();
+        let mut clipped = current.clamp();
+
+        if delta_e_ok(&clipped, &current) < GAMUT_JND {
+            return clipped;
+        }
+
+        let (mut min, mut max) = (0.0, origin.c);
+        let mut min_in_gamut = true;
+
+        while max - min > GAMUT_MAP_EPSILON {
+            let chroma = (min + max) / 2.0;
+            current = Self::from_oklch(origin.l, chroma, origin.h, alpha);
+
+            if min_in_gamut && current.is_in_gamut() {
+                min = chroma;
+                continue;
+            }
+
+            clipped = current.clamp();
+            let e = delta_e_ok(&clipped, &current);
+
+            if e < GAMUT_JND {
+                if GAMUT_JND - e < GAMUT_MAP_EPSILON {
+                    return clipped;
+                }
+                min_in_gamut = false;
+                min = chroma;
+            } else {
+                max = chroma;
+            }
+        }
+
+        if current.is_in_gamut() {
+            return current.clamp();
+        }
+
+        clipped
+    }
+
     /// Arguments:
     ///
     /// * `r`: Red value [0..255]
@@ -130,13 +254,7 @@ impl Solid {
     /// * `b`: Blue value [0..1]
     /// * `a`: Alpha value [0..1]
     pub fn from_normalized_linear_rgba(r: f32, g: f32, b: f32, a: f32) -> Self {
-        fn from_linear(x: f32) -> f32 {
-            if x >= 0.0031308 {
-                return 1.055 * x.powf(1.0 / 2.4) - 0.055;
-            }
-            12.92 * x
-        }
-        Self::new(from_linear(r), from_linear(g), from_linear(b), a)
+        Self::new(linear_to_srgb(r), linear_to_srgb(g), linear_to_srgb(b), a)
     }
 
     /// Arguments:
@@ -203,6 +321,91 @@ impl Solid {
         Self::new(clamp0_1(r), clamp0_1(g), clamp0_1(b), clamp0_1(a))
     }
 
+    /// Arguments:
+    ///
+    /// * `h`: Hue angle [0..360]
+    /// * `w`: Whiteness [0..100]
+    /// * `b`: Blackness [0..100]
+    /// * `a`: Alpha [0..1]
+    pub fn from_hwba(h: f32, w: f32, b: f32, a: f32) -> Self {
+        let (r, g, b) = hwb_to_rgb(normalize_angle(h), clamp0_1(w / 100.0), clamp0_1(b / 100.0));
+
+        Self::new(clamp0_1(r), clamp0_1(g), clamp0_1(b), clamp0_1(a))
+    }
+
+    /// The result may lie outside the sRGB gamut, see [`Solid::clamp`].
+    ///
+    /// Arguments:
+    ///
+    /// * `l`: Lightness [0..100]
+    /// * `a`: Green-red axis [-125..125]
+    /// * `b`: Blue-yellow axis [-125..125]
+    /// * `alpha`: Alpha [0..1]
+    pub fn from_lab(l: f32, a: f32, b: f32, alpha: f32) -> Self {
+        let (r, g, b) = lab_to_rgb(l, a, b);
+
+        Self::new(r, g, b, clamp0_1(alpha))
+    }
+
+    /// The result may lie outside the sRGB gamut, see [`Solid::clamp`].
+    ///
+    /// Arguments:
+    ///
+    /// * `l`: Lightness [0..100]
+    /// * `c`: Chroma [0..150]
+    /// * `h`: Hue angle [0..360]
+    /// * `alpha`: Alpha [0..1]
+    pub fn from_lch(l: f32, c: f32, h: f32, alpha: f32) -> Self {
+        let (a, b) = lch_to_lab(c.max(0.0), normalize_angle(h));
+
+        Self::from_lab(l, a, b, alpha)
+    }
+
+    /// The result may lie outside the sRGB gamut, see [`Solid::clamp`].
+    ///
+    /// Arguments:
+    ///
+    /// * `l`: Lightness [0..1]
+    /// * `a`: Green-red axis [-0.4..0.4]
+    /// * `b`: Blue-yellow axis [-0.4..0.4]
+    /// * `alpha`: Alpha [0..1]
+    pub fn from_oklab(l: f32, a: f32, b: f32, alpha: f32) -> Self {
+        let (r, g, b) = oklab_to_rgb(l, a, b);
+
+        Self::new(r, g, b, clamp0_1(alpha))
+    }
+
+    /// The result may lie outside the sRGB gamut, see [`Solid::clamp`].
+    ///
+    /// Arguments:
+    ///
+    /// * `l`: Lightness [0..1]
+    /// * `c`: Chroma [0..0.4]
+    /// * `h`: Hue angle [0..360]
+    /// * `alpha`: Alpha [0..1]
+    pub fn from_oklch(l: f32, c: f32, h: f32, alpha: f32) -> Self {
+        let (a, b) = lch_to_lab(c.max(0.0), normalize_angle(h));
+
+        Self::from_oklab(l, a, b, alpha)
+    }
+
+    /// Create `Solid` from the components of a color in the given color space.
+    ///
+    /// Colors outside the sRGB gamut (e.g. saturated `display-p3` colors) keep their
+    /// out-of-range channels, so [`Solid::to_colorspace`] gives back the original components.
+    /// Use [`Solid::gamut_map`] or [`Solid::clamp`] to bring them into the sRGB gamut.
+    ///
+    /// Arguments:
+    ///
+    /// * `space`: The color space of `c`
+    /// * `c`: The three components, see [`Colorspace`] for their ranges
+    /// * `alpha`: Alpha [0..1]
+    pub fn from_colorspace(space: Colorspace, c: [f32; 3], alpha: f32) -> Self {
+        let (r, g, b) = colorspace_to_rgb(space, c);
+
+        Self::new(r, g, b, clamp0_1(alpha))
+    }
+
     /// Create `Solid` from CSS color string.
     pub fn from_html<S: AsRef<str>>(s: S) -> SolidResult<Solid> {
         parse_solid(s.as_ref(), None)
@@ -213,6 +416,22 @@ impl Solid {
         parse_solid(s.as_ref(), Some(path.as_ref()))
     }
 
+    /// Create `Solid` from CSS color string, using the given parse options.
+    pub fn from_html_with_options<S: AsRef<str>>(
+        s: S,
+        options: &ParseOptions,
+    ) -> SolidResult<Solid> {
+        parse_solid_with_options(s.as_ref(), options)
+    }
+
+    /// Create `Solid` from CSS color string, resolving `light-dark()` for the given color scheme.
+    pub fn from_html_with_color_scheme<S: AsRef<str>>(
+        s: S,
+        color_scheme: ColorScheme,
+    ) -> SolidResult<Solid> {
+        parse_solid_with_options(s.as_ref(), &ParseOptions::with_color_scheme(color_scheme))
+    }
+
     #[cfg(feature = "named-colors")]
     pub fn name(&self) -> Option<&'static str> {
         let rgb = self.to_rgba();
@@ -251,20 +470,102 @@ impl Solid {
         NormalizedHsla { h, s, l, a: self.3 }
     }
 
+    /// Returns: `[h, w, b, a]`
+    ///
+    /// * `h`: Hue angle [0..360]
+    /// * `w`: Whiteness [0..100]
+    /// * `b`: Blackness [0..100]
+    /// * `a`: Alpha [0..1]
+    pub fn to_hwba(&self) -> Hwba {
+        let (h, w, b) = rgb_to_hwb(self.0, self.1, self.2);
+        Hwba {
+            h,
+            w: w.mul(100.0),
+            b: b.mul(100.0),
+            a: self.3,
+        }
+    }
+
+    /// Returns: `[l, a, b, alpha]` (CIE Lab, D50 white point)
+    ///
+    /// * `l`: Lightness [0..100]
+    /// * `a`: Green-red axis [-125..125]
+    /// * `b`: Blue-yellow axis [-125..125]
+    /// * `alpha`: Alpha [0..1]
+    pub fn to_lab(&self) -> Lab {
+        let (l, a, b) = rgb_to_lab(self.0, self.1, self.2);
+        Lab {
+            l,
+            a,
+            b,
+            alpha: self.3,
+        }
+    }
+
+    /// Returns: `[l, c, h, alpha]` (CIE LCH, D50 white point)
+    ///
+    /// * `l`: Lightness [0..100]
+    /// * `c`: Chroma [0..150]
+    /// * `h`: Hue angle [0..360]
+    /// * `alpha`: Alpha [0..1]
+    pub fn to_lch(&self) -> Lch {
+        let (l, a, b) = rgb_to_lab(self.0, self.1, self.2);
+        let (c, h) = lab_to_lch(a, b);
+        Lch {
+            l,
+            c,
+            h,
+            alpha: self.3,
+        }
+    }
+
+    /// Returns: `[l, a, b, alpha]`
+    ///
+    /// * `l`: Lightness [0..1]
+    /// * `a`: Green-red axis [-0.4..0.4]
+    /// * `b`: Blue-yellow axis [-0.4..0.4]
+    /// * `alpha`: Alpha [0..1]
+    pub fn to_oklab(&self) -> Oklab {
+        let (l, a, b) = rgb_to_oklab(self.0, self.1, self.2);
+        Oklab {
+            l,
+            a,
+            b,
+            alpha: self.3,
+        }
+    }
+
+    /// Returns: `[l, c, h, alpha]`
+    ///
+    /// * `l`: Lightness [0..1]
+    /// * `c`: Chroma [0..0.4]
+    /// * `h`: Hue angle [0..360]
+    /// * `alpha`: Alpha [0..1]
+    pub fn to_oklch(&self) -> Oklch {
+        let (l, a, b) = rgb_to_oklab(self.0, self.1, self.2);
+        let (c, h) = lab_to_lch(a, b);
+        Oklch {
+            l,
+            c,
+            h,
+            alpha: self.3,
+        }
+    }
+
+    /// Returns the three components of this color in the given color space, see [`Colorspace`]
+    /// for their ranges. Alpha is left out, see [`Solid::to_array`].
+    pub fn to_colorspace(&self, space: Colorspace) -> [f32; 3] {
+        rgb_to_colorspace(space, self.0, self.1, self.2)
+    }
+
     /// Returns: `[r, g, b, a]`
     ///
     /// * Red, green, blue and alpha in the range [0..1]
     pub fn to_normalized_linear_rgba(&self) -> NormalizedRgba {
-        fn to_linear(x: f32) -> f32 {
-            if x >= 0.04045 {
-                return ((x + 0.055) / 1.055).powf(2.4);
-            }
-            x / 12.92
-        }
         NormalizedRgba {
-            r: to_linear(self.0),
-            g: to_linear(self.1),
-            b: to_linear(self.2),
+            r: srgb_to_linear(self.0),
+            g: srgb_to_linear(self.1),
+            b: srgb_to_linear(self.2),
             a: self.3,
         }
     }
@@ -285,7 +586,7 @@ impl Solid {
     /// Get the RGB hexadecimal color string.
     pub fn to_hex_string(&self) -> String {
         let rgba = self.to_rgba();
-        let alpha = rgba.a.mul_add(255.0, 0.5).round() as u8;
+        let alpha = rgba.a.mul(255.0).round() as u8;
 
         if alpha < 255 {
             return format!("#{:02x}{:02x}{:02x}{:02x}", rgba.r, rgba.g, rgba.b, alpha);
@@ -294,6 +595,64 @@ impl Solid {
         format!("#{:02x}{:02x}{:02x}", rgba.r, rgba.g, rgba.b)
     }
 
+    /// Get a CSS color string that parses back to this color.
+    ///
+    /// This is the HEX string for colors with 8-bit channels and no missing components, and
+    /// `color(srgb r g b / a)` for other colors. Colors with missing components of another color
+    /// space are written in that space instead, with these components as `none`, e.g.
+    /// `hsl(none 0% 50%)`. Their other components may then be off by a rounding error when the
+    /// conversion to that space and back does not give the exact same sRGB channels.
+    pub fn to_css_string(&self) -> String {
+        let rgba = self.to_rgba();
+        let alpha = rgba.a.mul(255.0).round() as u8;
+
+        if self.4 == 0 && Self::from_rgba8(rgba.r, rgba.g, rgba.b, alpha) == *self {
+            return self.to_hex_string();
+        }
+
+        let spaces = self.missing_spaces();
+        if spaces.is_empty() {
+            return self.to_css_function(Colorspace::Srgb, self.to_colorspace(Colorspace::Srgb));
+        }
+
+        // The components are converted back from sRGB, so they may be a rounding error away
+        // from the ones that give this exact color: try them rounded to fewer decimals (as
+        // written by hand), then their neighbors. Otherwise, take the closest color.
+        let first = self.to_css_function(spaces[0], self.to_colorspace(spaces[0]));
+        let mut closest = (f32::INFINITY, first);
+        for space in spaces {
+            let c = self.to_colorspace(space);
+            let rounded = (0..7).map(|decimals| {
+                let scale = 10f32.powi(decimals);
+                c.map(|t| (t * scale).round() / scale)
+            });
+            let nearby = |t: f32| [t, t.next_up(), t.next_down()];
+            let neighbors = nearby(c[0]).into_iter().flat_map(|c0| {
+                nearby(c[1])
+                    .into_iter()
+                    .flat_map(move |c1| nearby(c[2]).map(|c2| [c0, c1, c2]))
+            });
+
+            for c in rounded.chain(neighbors) {
+                let css = self.to_css_function(space, c);
+                let Ok(solid) = parse_solid(&css, None) else {
+                    continue;
+                };
+                if solid == *self {
+                    return css;
+                }
+
+                let [a, b] = [self.to_array(), solid.to_array()];
+                let distance = (0..4).map(|i| (a[i] - b[i]).abs()).fold(0.0, f32::max);
+                if distance < closest.0 {
+                    closest = (distance, css);
+                }
+            }
+        }
+
+        closest.1
+    }
+
     /// Get the CSS `rgb()` format string.
     pub fn to_rgb_string(&self) -> String {
         let rgba = self.to_rgba();
@@ -305,27 +664,331 @@ impl Solid {
         format!("rgb({},{},{})", rgba.r, rgba.g, rgba.b)
     }
 
-    /// Blend this color with the other one, in the RGB color-space. `t` in the range [0..1].
-    pub fn interpolate_rgb(&self, other: &Solid, t: f32) -> Self {
+    /// Make this color darker by decreasing its HSL lightness. `amount` in the range [0..1].
+    pub fn darken(&self, amount: f32) -> Self {
+        let hsla = self.to_normalized_hsla();
+        Self::from_normalized_hsla(hsla.h, hsla.s, hsla.l - amount, hsla.a)
+    }
+
+    /// Make this color lighter by increasing its HSL lightness. `amount` in the range [0..1].
+    pub fn lighten(&self, amount: f32) -> Self {
+        let hsla = self.to_normalized_hsla();
+        Self::from_normalized_hsla(hsla.h, hsla.s, hsla.l + amount, hsla.a)
+    }
+
+    /// Make this color more saturated by increasing its HSL saturation. `amount` in the range [0..1].
+    pub fn saturate(&self, amount: f32) -> Self {
+        let hsla = self.to_normalized_hsla();
+        Self::from_normalized_hsla(hsla.h, hsla.s + amount, hsla.l, hsla.a)
+    }
+
+    /// Make this color less saturated by decreasing its HSL saturation. `amount` in the range [0..1].
+    pub fn desaturate(&self, amount: f32) -> Self {
+        let hsla = self.to_normalized_hsla();
+        Self::from_normalized_hsla(hsla.h, hsla.s - amount, hsla.l, hsla.a)
+    }
+
+    /// Rotate the HSL hue of this color by `degrees`.
+    pub fn adjust_hue(&self, degrees: f32) -> Self {
+        let hsla = self.to_normalized_hsla();
+        Self::from_normalized_hsla(hsla.h + degrees, hsla.s, hsla.l, hsla.a)
+    }
+
+    /// Remove all saturation from this color.
+    pub fn grayscale(&self) -> Self {
+        self.desaturate(1.0)
+    }
+
+    /// Get the complement of this color (the hue rotated by 180 degrees).
+    pub fn complement(&self) -> Self {
+        self.adjust_hue(180.0)
+    }
+
+    /// Invert the red, green and blue channels of this color.
+    /// `weight` in the range [0..1] is how much of the inverted color is used.
+    pub fn invert(&self, weight: f32) -> Self {
+        Self::new(1.0 - self.0, 1.0 - self.1, 1.0 - self.2, self.3).mix(self, weight)
+    }
+
+    /// Set the alpha of this color. `alpha` in the range [0..1].
+    pub fn fade(&self, alpha: f32) -> Self {
+        Self::new(self.0, self.1, self.2, clamp0_1(alpha))
+    }
+
+    /// Make this color more opaque by increasing its alpha. `amount` in the range [0..1].
+    pub fn fade_in(&self, amount: f32) -> Self {
+        self.fade(self.3 + amount)
+    }
+
+    /// Make this color more transparent by decreasing its alpha. `amount` in the range [0..1].
+    pub fn fade_out(&self, amount: f32) -> Self {
+        self.fade(self.3 - amount)
+    }
+
+    /// Mix this color with white. `weight` in the range [0..1] is how much white is used.
+    pub fn tint(&self, weight: f32) -> Self {
+        Self::new(1.0, 1.0, 1.0, 1.0).mix(self, weight)
+    }
+
+    /// Mix this color with black. `weight` in the range [0..1] is how much black is used.
+    pub fn shade(&self, weight: f32) -> Self {
+        Self::new(0.0, 0.0, 0.0, 1.0).mix(self, weight)
+    }
+
+    /// Mix this color with the other one, the same way Sass `mix()` does.
+    /// `weight` in the range [0..1] is how much of this color is used; alpha is taken into account.
+    pub fn mix(&self, other: &Solid, weight: f32) -> Self {
+        let weight = clamp0_1(weight);
+        let w = 2.0 * weight - 1.0;
+        let a = self.3 - other.3;
+
+        let w1 = if w * a == -1.0 {
+            (w + 1.0) / 2.0
+        } else {
+            ((w + a) / (1.0 + w * a) + 1.0) / 2.0
+        };
+        let w2 = 1.0 - w1;
+
         Self::new(
-            self.0 + t * (other.0 - self.0),
-            self.1 + t * (other.1 - self.1),
-            self.2 + t * (other.2 - self.2),
-            self.3 + t * (other.3 - self.3),
+            self.0 * w1 + other.0 * w2,
+            self.1 * w1 + other.1 * w2,
+            self.2 * w1 + other.2 * w2,
+            self.3 * weight + other.3 * (1.0 - weight),
         )
     }
 
+    /// Blend this color with the other one, in the RGB color-space. `t` in the range [0..1].
+    pub fn interpolate_rgb(&self, other: &Solid, t: f32) -> Self {
+        self.interpolate_straight(other, t, Colorspace::Srgb, HueInterpolation::default())
+    }
+
     /// Blend this color with the other one, in the linear RGB color-space. `t` in the range [0..1].
     pub fn interpolate_linear_rgb(&self, other: &Solid, t: f32) -> Self {
-        let rgba_1 = self.to_normalized_linear_rgba();
-        let rgba_2 = other.to_normalized_linear_rgba();
-        Self::from_normalized_linear_rgba(
-            rgba_1.r + t * (rgba_2.r - rgba_1.r),
-            rgba_1.g + t * (rgba_2.g - rgba_1.g),
-            rgba_1.b + t * (rgba_2.b - rgba_1.b),
-            rgba_1.a + t * (rgba_2.a - rgba_1.a),
+        self.interpolate_straight(
+            other,
+            t,
+            Colorspace::SrgbLinear,
+            HueInterpolation::default(),
+        )
+    }
+
+    /// Blend this color with the other one, in any color-space, the way CSS does. `t` in the
+    /// range [0..1].
+    ///
+    /// Colors are interpolated with premultiplied alpha. Missing components (see
+    /// [`Solid::is_missing`]) and the hue of an achromatic color (e.g. grey) take the other
+    /// color's value. `hue` only matters for the polar color-spaces (`hsl`, `hwb`, `lch` and
+    /// `oklch`).
+    pub fn interpolate(
+        &self,
+        other: &Solid,
+        t: f32,
+        space: Colorspace,
+        hue: HueInterpolation,
+    ) -> Self {
+        let (c1, c2, missing) = self.interpolation_components(other, space);
+        let hue_index = space.hue_index();
+
+        let (a1, a2) = (c1[3], c2[3]);
+        let alpha = a1 + t * (a2 - a1);
+
+        let c = std::array::from_fn(|i| {
+            if i == 3 {
+                alpha
+            } else if Some(i) == hue_index {
+                hue.interpolate(c1[i], c2[i], t)
+            } else if alpha > 0.0 {
+                (c1[i] * a1 + t * (c2[i] * a2 - c1[i] * a1)) / alpha
+            } else {
+                c1[i] + t * (c2[i] - c1[i])
+            }
+        });
+
+        Self::from_interpolated(space, c, missing)
+    }
+
+    /// Blend this color with the other one, in the OKLab color-space. `t` in the range [0..1].
+    pub fn interpolate_oklab(&self, other: &Solid, t: f32) -> Self {
+        self.interpolate_straight(other, t, Colorspace::Oklab, HueInterpolation::default())
+    }
+
+    /// Blend this color with the other one, in the OKLCH color-space. `t` in the range [0..1].
+    ///
+    /// Missing components and the hue of an achromatic color (e.g. grey) take the other
+    /// color's value.
+    pub fn interpolate_oklch(&self, other: &Solid, t: f32, hue: HueInterpolation) -> Self {
+        self.interpolate_straight(other, t, Colorspace::Oklch, hue)
+    }
+
+    /// Interpolation without premultiplied alpha, used by the `interpolate_*` methods.
+    fn interpolate_straight(
+        &self,
+        other: &Solid,
+        t: f32,
+        space: Colorspace,
+        hue: HueInterpolation,
+    ) -> Self {
+        let (c1, c2, missing) = self.interpolation_components(other, space);
+
+        let c = std::array::from_fn(|i| match space.hue_index() {
+            Some(h) if h == i => hue.interpolate(c1[i], c2[i], t),
+            _ => c1[i] + t * (c2[i] - c1[i]),
+        });
+
+        Self::from_interpolated(space, c, missing)
+    }
+
+    /// Returns the components of both colors in `space` followed by alpha, with the missing
+    /// components of one color replaced by the other's, and which components are missing in
+    /// both.
+    fn interpolation_components(
+        &self,
+        other: &Solid,
+        space: Colorspace,
+    ) -> ([f32; 4], [f32; 4], [bool; 4]) {
+        let (mut c1, m1) = self.components_in(space);
+        let (mut c2, m2) = other.components_in(space);
+
+        for i in 0..4 {
+            match (m1[i], m2[i]) {
+                (true, false) => c1[i] = c2[i],
+                (false, true) => c2[i] = c1[i],
+                _ => {}
+            }
+        }
+
+        (c1, c2, std::array::from_fn(|i| m1[i] && m2[i]))
+    }
+
+    /// Returns the components of this color in `space` followed by alpha, and which of them
+    /// are missing: analogous to a missing component of this color, or a powerless hue.
+    fn components_in(&self, space: Colorspace) -> ([f32; 4], [bool; 4]) {
+        let c = self.to_colorspace(space);
+        let mut missing = space
+            .components()
+            .map(|component| component.is_some_and(|component| self.is_missing(component)));
+
+        if let Some(i) = space.hue_index()
+            && has_powerless_hue(space, &c)
+        {
+            missing[i] = true;
+        }
+
+        (
+            [c[0], c[1], c[2], self.3],
+            [
+                missing[0],
+                missing[1],
+                missing[2],
+                self.is_missing(ColorComponent::Alpha),
+            ],
         )
     }
+
+    /// The color spaces holding all the missing components of this color but alpha (none when
+    /// there are no such components), or the one holding most of them when no color space
+    /// holds them all.
+    fn missing_spaces(&self) -> Vec<Colorspace> {
+        let spaces = [
+            Colorspace::Srgb,
+            Colorspace::SrgbLinear,
+            Colorspace::DisplayP3,
+            Colorspace::A98Rgb,
+            Colorspace::ProphotoRgb,
+            Colorspace::Rec2020,
+            Colorspace::XyzD50,
+            Colorspace::XyzD65,
+            Colorspace::Hsl,
+            Colorspace::Hwb,
+            Colorspace::Lab,
+            Colorspace::Lch,
+            Colorspace::Oklab,
+            Colorspace::Oklch,
+        ];
+        let missing = |space: &Colorspace| {
+            space
+                .components()
+                .into_iter()
+                .flatten()
+                .filter(|component| self.is_missing(*component))
+                .count()
+        };
+
+        let all = (self.4 & !component_bit(ColorComponent::Alpha)).count_ones() as usize;
+        if all == 0 {
+            return Vec::new();
+        }
+
+        let holding = spaces
+            .into_iter()
+            .filter(|space| missing(space) == all)
+            .collect::<Vec<_>>();
+        match holding.is_empty() {
+            true => spaces
+                .into_iter()
+                .rev()
+                .max_by_key(missing)
+                .into_iter()
+                .collect(),
+            false => holding,
+        }
+    }
+
+    /// The CSS function of `space` with the components `c`, `none` for the missing ones.
+    fn to_css_function(&self, space: Colorspace, c: [f32; 3]) -> String {
+        let missing = space
+            .components()
+            .map(|component| component.is_some_and(|component| self.is_missing(component)));
+        let [c0, c1, c2] = std::array::from_fn(|i| match (missing[i], space) {
+            (true, _) => "none".to_string(),
+            (false, Colorspace::Hsl | Colorspace::Hwb) if i > 0 => format!("{}%", c[i]),
+            (false, _) => c[i].to_string(),
+        });
+
+        let function = match space.is_predefined() {
+            true => format!("color({space} {c0} {c1} {c2}"),
+            false => format!("{space}({c0} {c1} {c2}"),
+        };
+
+        match (self.3, self.is_missing(ColorComponent::Alpha)) {
+            (_, true) => format!("{function} / none)"),
+            (1.0, false) => format!("{function})"),
+            (a, false) => format!("{function} / {a})"),
+        }
+    }
+
+    /// Creates the result of an interpolation, keeping the components missing in both colors.
+    fn from_interpolated(space: Colorspace, c: [f32; 4], missing: [bool; 4]) -> Self {
+        let components = space
+            .components()
+            .into_iter()
+            .chain([Some(ColorComponent::Alpha)]);
+
+        components
+            .zip(missing)
+            .filter_map(|(component, missing)| component.filter(|_| missing))
+            .fold(
+                Self::from_colorspace(space, [c[0], c[1], c[2]], c[3]),
+                Self::with_missing,
+            )
+    }
+}
+
+fn component_bit(component: ColorComponent) -> u16 {
+    1 << component as u16
+}
+
+/// Whether the hue of a color in a polar color space carries no meaning, because the color is
+/// achromatic.
+fn has_powerless_hue(space: Colorspace, c: &[f32; 3]) -> bool {
+    match space {
+        Colorspace::Hsl => c[1] <= 0.0 || c[2] <= 0.0 || c[2] >= 100.0,
+        Colorspace::Hwb => c[1] + c[2] >= 100.0,
+        // Lab chroma is roughly 250 times larger than OKLCH chroma.
+        Colorspace::Lch => c[1] < ACHROMATIC_CHROMA * 250.0,
+        Colorspace::Oklch => c[1] < ACHROMATIC_CHROMA,
+        _ => false,
+    }
 }
 
 impl Default for Solid {
@@ -468,11 +1131,11 @@ impl From<NormalizedRgba> for Solid {
     }
 }
 
-/// Implement Serde serialization into HEX string
+/// Implement Serde serialization into a CSS color string
 #[cfg(feature = "serde")]
 impl Serialize for Solid {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        serializer.serialize_str(&self.to_hex_string())
+        serializer.serialize_str(&self.to_css_string())
     }
 }
 
@@ -502,3 +1165,18 @@ impl Visitor<'_> for SolidVisitor {
         Solid::from_str(v).map_err(serde::de::Error::custom)
     }
 }
+
+/// Describe the CSS string that Serde (de)serializes as a JSON Schema string
+#[cfg(feature = "schema")]
+impl JsonSchema for Solid {
+    fn schema_name() -> Cow<'static, str> {
+        "Solid".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "A CSS color, e.g. `#ff0000` or `oklch(70% 0.1 250)`",
+        })
+    }
+}
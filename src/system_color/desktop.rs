@@ -0,0 +1,343 @@
+// Accent colors of Linux desktops, read from their configuration files.
+use std::env;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+use crate::ColorScheme;
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+use crate::Solid;
+use crate::parser::parse_solid;
+
+use super::{DefaultSystemColors, SystemColor, SystemColorProvider, without_system_colors};
+
+/// The GTK color names holding the accent color, in order of preference.
+const GTK_ACCENT_NAMES: [&str; 3] = ["accent_color", "accent_bg_color", "theme_selected_bg_color"];
+
+/// How many `@name` references a GTK color may go through.
+const GTK_MAX_REFERENCES: usize = 8;
+
+/// A configuration file holding the accent color of a desktop.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AccentSource {
+    /// KDE Plasma `kdeglobals`: `AccentColor` of the `[General]` group, falling back to
+    /// `BackgroundNormal` of the `[Colors:Selection]` group.
+    Kde(PathBuf),
+    /// GTK `gtk.css` or `colors.css`: `@define-color accent_color`, falling back to
+    /// `accent_bg_color` and `theme_selected_bg_color`.
+    Gtk(PathBuf),
+    /// A file holding a single CSS color, e.g. written by a script or a settings portal.
+    File(PathBuf),
+}
+
+impl AccentSource {
+    /// `kdeglobals` in the user configuration directory.
+    pub fn kde() -> Option<Self> {
+        config_dir().map(|dir| Self::Kde(dir.join("kdeglobals")))
+    }
+
+    /// `gtk.css` of GTK 4 in the user configuration directory.
+    pub fn gtk4() -> Option<Self> {
+        config_dir().map(|dir| Self::Gtk(dir.join("gtk-4.0").join("gtk.css")))
+    }
+
+    /// `gtk.css` of GTK 3 in the user configuration directory.
+    pub fn gtk3() -> Option<Self> {
+        config_dir().map(|dir| Self::Gtk(dir.join("gtk-3.0").join("gtk.css")))
+    }
+
+    /// Reads the accent color from the file.
+    ///
+    /// The color may not use system colors or `accent`, which would make
+    /// [`DesktopSystemColors`] read the file again.
+    pub fn read(&self) -> Result<Solid> {
+        let (path, color) = without_system_colors(|| match self {
+            Self::Kde(path) => read(path).map(|s| (path, parse_kdeglobals(&s))),
+            Self::Gtk(path) => read(path).map(|s| (path, parse_gtk_css(&s))),
+            Self::File(path) => read(path).map(|s| (path, parse_solid(s.trim(), None).ok())),
+        })?;
+
+        color.ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidSystemColor,
+                format!("no accent color in {}", path.display()),
+            )
+        })
+    }
+}
+
+/// A [`SystemColorProvider`] taking `AccentColor` (and so `accent`) from the first
+/// [`AccentSource`] that can be read, and every other color from [`DefaultSystemColors`].
+///
+/// ```no_run
+/// use colorparser_css::{DesktopSystemColors, set_system_color_provider};
+///
+/// set_system_color_provider(DesktopSystemColors::detect());
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct DesktopSystemColors {
+    sources: Vec<AccentSource>,
+}
+
+impl DesktopSystemColors {
+    /// Create a provider reading the given sources, in order.
+    pub fn new(sources: Vec<AccentSource>) -> Self {
+        Self { sources }
+    }
+
+    /// Create a provider reading the KDE and GTK configuration files in their default
+    /// locations, the ones of the current desktop (`XDG_CURRENT_DESKTOP`) first.
+    pub fn detect() -> Self {
+        let kde = env::var("XDG_CURRENT_DESKTOP")
+            .is_ok_and(|desktop| desktop.to_ascii_uppercase().contains("KDE"));
+
+        let sources = if kde {
+            [
+                AccentSource::kde(),
+                AccentSource::gtk4(),
+                AccentSource::gtk3(),
+            ]
+        } else {
+            [
+                AccentSource::gtk4(),
+                AccentSource::gtk3(),
+                AccentSource::kde(),
+            ]
+        };
+
+        Self::new(sources.into_iter().flatten().collect())
+    }
+
+    /// Adds a source, read after the existing ones.
+    pub fn with_source(mut self, source: AccentSource) -> Self {
+        self.sources.push(source);
+        self
+    }
+
+    /// The sources, in the order they are read.
+    pub fn sources(&self) -> &[AccentSource] {
+        &self.sources
+    }
+}
+
+impl SystemColorProvider for DesktopSystemColors {
+    fn system_color(&self, color: SystemColor, color_scheme: ColorScheme) -> Result<Solid> {
+        if color == SystemColor::AccentColor
+            && let Some(accent) = self.sources.iter().find_map(|source| source.read().ok())
+        {
+            return Ok(accent);
+        }
+
+        DefaultSystemColors.system_color(color, color_scheme)
+    }
+}
+
+/// `$XDG_CONFIG_HOME`, or `$HOME/.config`.
+fn config_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+}
+
+fn read(path: &Path) -> Result<String> {
+    read_to_string(path).map_err(|e| Error::new(ErrorKind::InvalidUnknown, format!("{:?}", e)))
+}
+
+/// Parses the accent color of a `kdeglobals` file, where colors are written as `r,g,b`.
+fn parse_kdeglobals(contents: &str) -> Option<Solid> {
+    let mut group = "";
+    let mut selection = None;
+
+    for line in contents.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            group = name;
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+
+        match (group, key.trim()) {
+            ("General", "AccentColor") => return parse_kde_color(value.trim()),
+            ("Colors:Selection", "BackgroundNormal") => selection = parse_kde_color(value.trim()),
+            _ => {}
+        }
+    }
+
+    selection
+}
+
+/// Parses `r,g,b` or `r,g,b,a` with channels in [0..255], or a hex color.
+fn parse_kde_color(s: &str) -> Option<Solid> {
+    if !s.contains(',') {
+        return parse_solid(s, None).ok();
+    }
+
+    let channels = s
+        .split(',')
+        .map(|c| c.trim().parse::<u8>().ok())
+        .collect::<Option<Vec<u8>>>()?;
+
+    match channels[..] {
+        [r, g, b] => Some(Solid::from_rgba8(r, g, b, 255)),
+        [r, g, b, a] => Some(Solid::from_rgba8(r, g, b, a)),
+        _ => None,
+    }
+}
+
+/// Parses the accent color of a GTK style sheet, following `@name` references between
+/// `@define-color` rules.
+fn parse_gtk_css(contents: &str) -> Option<Solid> {
+    let contents = strip_comments(contents);
+    let defines = contents
+        .split([';', '{', '}'])
+        .filter_map(|rule| {
+            let rule = rule.trim_start().strip_prefix("@define-color")?;
+            let (name, value) = rule.trim().split_once(char::is_whitespace)?;
+            Some((name, value.trim()))
+        })
+        .collect::<Vec<_>>();
+
+    let lookup = |name: &str| {
+        defines
+            .iter()
+            .rev()
+            .find(|(n, _)| *n == name)
+            .map(|(_, value)| *value)
+    };
+
+    GTK_ACCENT_NAMES.iter().find_map(|name| {
+        let mut value = lookup(name)?;
+        for _ in 0..GTK_MAX_REFERENCES {
+            match value.strip_prefix('@') {
+                Some(name) => value = lookup(name)?,
+                None => return parse_solid(value, None).ok(),
+            }
+        }
+        None
+    })
+}
+
+/// Removes the `/* ... */` comments of a style sheet.
+fn strip_comments(mut s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+
+    while let Some(start) = s.find("/*") {
+        result.push_str(&s[..start]);
+        match s[start + 2..].find("*/") {
+            Some(end) => s = &s[start + 2 + end + 2..],
+            None => return result,
+        }
+    }
+
+    result.push_str(s);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+    }
+
+    #[test]
+    fn test_kde_accent() {
+        let accent = AccentSource::Kde(fixture("kdeglobals")).read().unwrap();
+        assert_eq!(accent.to_hex_string(), "#3daee9");
+
+        let selection = AccentSource::Kde(fixture("kdeglobals_selection"))
+            .read()
+            .unwrap();
+        assert_eq!(selection.to_hex_string(), "#2980b9");
+    }
+
+    #[test]
+    fn test_gtk_accent() {
+        let accent = AccentSource::Gtk(fixture("gtk.css")).read().unwrap();
+        assert_eq!(accent.to_hex_string(), "#3584e4");
+
+        let selected = AccentSource::Gtk(fixture("colors.css")).read().unwrap();
+        assert_eq!(selected.to_hex_string(), "#5294e2");
+    }
+
+    #[test]
+    fn test_file_accent() {
+        let accent = AccentSource::File(fixture("accent")).read().unwrap();
+        assert_eq!(accent.to_hex_string(), "#89b4fa");
+    }
+
+    #[test]
+    fn test_missing_accent() {
+        let cases = [
+            AccentSource::Kde(fixture("gtk.css")),
+            AccentSource::Gtk(fixture("kdeglobals")),
+            AccentSource::File(fixture("does-not-exist")),
+        ];
+
+        for source in cases {
+            assert!(source.read().is_err(), "{source:?}");
+        }
+    }
+
+    #[test]
+    fn test_recursive_accent() {
+        // Resolving these through the provider that reads them would never end.
+        let cases = [
+            AccentSource::Kde(fixture("kdeglobals_recursive")),
+            AccentSource::File(fixture("accent_recursive")),
+        ];
+
+        for source in cases {
+            let err = source.read().unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidSystemColor, "{source:?}");
+        }
+
+        let selected = AccentSource::Gtk(fixture("gtk_recursive.css"))
+            .read()
+            .unwrap();
+        assert_eq!(selected.to_hex_string(), "#5294e2");
+
+        let provider =
+            DesktopSystemColors::new(vec![AccentSource::File(fixture("accent_recursive"))]);
+        assert_eq!(
+            provider.accent(ColorScheme::Light).unwrap(),
+            DefaultSystemColors.accent(ColorScheme::Light).unwrap()
+        );
+        assert!(parse_solid("AccentColor", None).is_ok());
+    }
+
+    #[test]
+    fn test_desktop_provider() {
+        let provider = DesktopSystemColors::new(vec![
+            AccentSource::File(fixture("does-not-exist")),
+            AccentSource::Gtk(fixture("gtk.css")),
+        ])
+        .with_source(AccentSource::Kde(fixture("kdeglobals")));
+
+        let accent = provider.accent(ColorScheme::Light).unwrap();
+        assert_eq!(accent.to_hex_string(), "#3584e4");
+
+        let canvas = provider
+            .system_color(SystemColor::Canvas, ColorScheme::Dark)
+            .unwrap();
+        assert_eq!(
+            canvas,
+            DefaultSystemColors
+                .system_color(SystemColor::Canvas, ColorScheme::Dark)
+                .unwrap()
+        );
+
+        let empty = DesktopSystemColors::default();
+        assert_eq!(
+            empty.accent(ColorScheme::Light).unwrap(),
+            DefaultSystemColors.accent(ColorScheme::Light).unwrap()
+        );
+    }
+}
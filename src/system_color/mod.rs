@@ -0,0 +1,375 @@
+use core::fmt;
+use std::cell::Cell;
+use std::sync::{Arc, LazyLock, RwLock};
+
+use crate::ColorScheme;
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+use crate::Solid;
+
+pub use desktop::{AccentSource, DesktopSystemColors};
+
+mod desktop;
+
+/// The [CSS system colors](https://www.w3.org/TR/css-color-4/#css-system-colors), resolved
+/// through the installed [`SystemColorProvider`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SystemColor {
+    /// `AccentColor`: background of accented user interface controls.
+    AccentColor,
+    /// `AccentColorText`: text of accented user interface controls.
+    AccentColorText,
+    /// `ActiveText`: text in active links.
+    ActiveText,
+    /// `ButtonBorder`: base border color of controls.
+    ButtonBorder,
+    /// `ButtonFace`: background color of controls.
+    ButtonFace,
+    /// `ButtonText`: text color of controls.
+    ButtonText,
+    /// `Canvas`: background of application content or documents.
+    Canvas,
+    /// `CanvasText`: text color in application content or documents.
+    CanvasText,
+    /// `Field`: background of input fields.
+    Field,
+    /// `FieldText`: text in input fields.
+    FieldText,
+    /// `GrayText`: text color for disabled items.
+    GrayText,
+    /// `Highlight`: background of selected items.
+    Highlight,
+    /// `HighlightText`: text color of selected items.
+    HighlightText,
+    /// `LinkText`: text in non-active, non-visited links.
+    LinkText,
+    /// `Mark`: background of text that has been specially marked.
+    Mark,
+    /// `MarkText`: text that has been specially marked.
+    MarkText,
+    /// `SelectedItem`: background of selected items, for example a selected checkbox.
+    SelectedItem,
+    /// `SelectedItemText`: text of selected items.
+    SelectedItemText,
+    /// `VisitedText`: text in visited links.
+    VisitedText,
+}
+
+impl SystemColor {
+    /// The name of the system color in CSS.
+    pub fn css_name(&self) -> &'static str {
+        match self {
+            Self::AccentColor => "AccentColor",
+            Self::AccentColorText => "AccentColorText",
+            Self::ActiveText => "ActiveText",
+            Self::ButtonBorder => "ButtonBorder",
+            Self::ButtonFace => "ButtonFace",
+            Self::ButtonText => "ButtonText",
+            Self::Canvas => "Canvas",
+            Self::CanvasText => "CanvasText",
+            Self::Field => "Field",
+            Self::FieldText => "FieldText",
+            Self::GrayText => "GrayText",
+            Self::Highlight => "Highlight",
+            Self::HighlightText => "HighlightText",
+            Self::LinkText => "LinkText",
+            Self::Mark => "Mark",
+            Self::MarkText => "MarkText",
+            Self::SelectedItem => "SelectedItem",
+            Self::SelectedItemText => "SelectedItemText",
+            Self::VisitedText => "VisitedText",
+        }
+    }
+}
+
+/// System color keywords are case-insensitive, e.g. `canvastext` is `CanvasText`.
+impl TryFrom<&str> for SystemColor {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "accentcolor" => Ok(Self::AccentColor),
+            "accentcolortext" => Ok(Self::AccentColorText),
+            "activetext" => Ok(Self::ActiveText),
+            "buttonborder" => Ok(Self::ButtonBorder),
+            "buttonface" => Ok(Self::ButtonFace),
+            "buttontext" => Ok(Self::ButtonText),
+            "canvas" => Ok(Self::Canvas),
+            "canvastext" => Ok(Self::CanvasText),
+            "field" => Ok(Self::Field),
+            "fieldtext" => Ok(Self::FieldText),
+            "graytext" => Ok(Self::GrayText),
+            "highlight" => Ok(Self::Highlight),
+            "highlighttext" => Ok(Self::HighlightText),
+            "linktext" => Ok(Self::LinkText),
+            "mark" => Ok(Self::Mark),
+            "marktext" => Ok(Self::MarkText),
+            "selecteditem" => Ok(Self::SelectedItem),
+            "selecteditemtext" => Ok(Self::SelectedItemText),
+            "visitedtext" => Ok(Self::VisitedText),
+            _ => Err(Error::new(ErrorKind::InvalidSystemColor, s)),
+        }
+    }
+}
+
+impl fmt::Display for SystemColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.css_name())
+    }
+}
+
+/// Resolves the CSS system colors, as well as the `accent` and `accent_inactive` keywords.
+///
+/// The default provider is [`DefaultSystemColors`]. Install your own with
+/// [`set_system_color_provider`], e.g. to follow the desktop theme or to use fixed colors in
+/// tests:
+///
+/// ```
+/// use colorparser_css::{ColorScheme, Result, Solid, SystemColor, SystemColorProvider};
+///
+/// struct Fixed;
+///
+/// impl SystemColorProvider for Fixed {
+///     fn system_color(&self, color: SystemColor, _: ColorScheme) -> Result<Solid> {
+///         match color {
+///             SystemColor::AccentColor => Solid::from_html("#89b4fa"),
+///             _ => Solid::from_html("#1e1e2e"),
+///         }
+///     }
+/// }
+///
+/// colorparser_css::set_system_color_provider(Fixed);
+/// assert_eq!(Solid::from_html("AccentColor").unwrap().to_hex_string(), "#89b4fa");
+/// assert_eq!(Solid::from_html("accent").unwrap().to_hex_string(), "#89b4fa");
+/// # colorparser_css::reset_system_color_provider();
+/// ```
+pub trait SystemColorProvider: Send + Sync {
+    /// Returns the value of a system color for the given color scheme.
+    fn system_color(&self, color: SystemColor, color_scheme: ColorScheme) -> Result<Solid>;
+
+    /// Returns the accent color of active windows, used by the `accent` keyword.
+    ///
+    /// Defaults to [`SystemColor::AccentColor`].
+    fn accent(&self, color_scheme: ColorScheme) -> Result<Solid> {
+        self.system_color(SystemColor::AccentColor, color_scheme)
+    }
+
+    /// Returns the accent color of inactive windows, used by the `accent_inactive` keyword.
+    ///
+    /// Defaults to a greyed out [`SystemColorProvider::accent`], the way Windows draws the
+    /// borders of inactive windows.
+    fn inactive_accent(&self, color_scheme: ColorScheme) -> Result<Solid> {
+        self.accent(color_scheme)
+            .map(|accent| inactive_accent(&accent))
+    }
+}
+
+/// The default [`SystemColorProvider`].
+///
+/// Returns a static table of colors, close to the ones of web browsers. On Windows, the accent
+/// colors come from the Desktop Window Manager colorization color instead.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DefaultSystemColors;
+
+impl SystemColorProvider for DefaultSystemColors {
+    fn system_color(&self, color: SystemColor, color_scheme: ColorScheme) -> Result<Solid> {
+        #[cfg(windows)]
+        if color == SystemColor::AccentColor {
+            return crate::utils::get_accent(true);
+        }
+
+        let [r, g, b] = match color_scheme {
+            ColorScheme::Light => light_system_color(color),
+            ColorScheme::Dark => dark_system_color(color),
+        };
+
+        Ok(Solid::from_rgba8(r, g, b, 255))
+    }
+
+    #[cfg(windows)]
+    fn inactive_accent(&self, _: ColorScheme) -> Result<Solid> {
+        crate::utils::get_accent(false)
+    }
+}
+
+fn light_system_color(color: SystemColor) -> [u8; 3] {
+    match color {
+        SystemColor::AccentColor | SystemColor::SelectedItem => [0x00, 0x78, 0xd4],
+        SystemColor::AccentColorText | SystemColor::SelectedItemText => [0xff, 0xff, 0xff],
+        SystemColor::ActiveText => [0xff, 0x00, 0x00],
+        SystemColor::ButtonBorder => [0x76, 0x76, 0x76],
+        SystemColor::ButtonFace => [0xef, 0xef, 0xef],
+        SystemColor::Canvas | SystemColor::Field => [0xff, 0xff, 0xff],
+        SystemColor::GrayText => [0x6d, 0x6d, 0x6d],
+        SystemColor::Highlight => [0xb5, 0xd5, 0xff],
+        SystemColor::LinkText => [0x00, 0x00, 0xee],
+        SystemColor::Mark => [0xff, 0xff, 0x00],
+        SystemColor::VisitedText => [0x55, 0x1a, 0x8b],
+        SystemColor::ButtonText
+        | SystemColor::CanvasText
+        | SystemColor::FieldText
+        | SystemColor::HighlightText
+        | SystemColor::MarkText => [0x00, 0x00, 0x00],
+    }
+}
+
+fn dark_system_color(color: SystemColor) -> [u8; 3] {
+    match color {
+        SystemColor::AccentColor | SystemColor::SelectedItem => [0x4c, 0xc2, 0xff],
+        SystemColor::AccentColorText | SystemColor::SelectedItemText => [0x00, 0x00, 0x00],
+        SystemColor::ActiveText => [0xff, 0x9e, 0x9e],
+        SystemColor::ButtonBorder => [0x6b, 0x6b, 0x6b],
+        SystemColor::ButtonFace => [0x3b, 0x3b, 0x3b],
+        SystemColor::Canvas => [0x12, 0x12, 0x12],
+        SystemColor::Field => [0x3b, 0x3b, 0x3b],
+        SystemColor::GrayText => [0x8e, 0x8e, 0x8e],
+        SystemColor::Highlight => [0x99, 0xc8, 0xff],
+        SystemColor::LinkText => [0x9e, 0x9e, 0xff],
+        SystemColor::Mark => [0x66, 0x66, 0x00],
+        SystemColor::VisitedText => [0xd0, 0xad, 0xf0],
+        SystemColor::HighlightText => [0x00, 0x00, 0x00],
+        SystemColor::ButtonText
+        | SystemColor::CanvasText
+        | SystemColor::FieldText
+        | SystemColor::MarkText => [0xff, 0xff, 0xff],
+    }
+}
+
+/// Greys out an accent color, the way Windows draws the borders of inactive windows.
+fn inactive_accent(accent: &Solid) -> Solid {
+    let [r, g, b, a] = accent.to_array();
+    let avg = (r + g + b) / 3.0;
+
+    Solid::new(
+        avg / 1.5 + r / 10.0,
+        avg / 1.5 + g / 10.0,
+        avg / 1.5 + b / 10.0,
+        a,
+    )
+}
+
+static SYSTEM_COLOR_PROVIDER: LazyLock<RwLock<Arc<dyn SystemColorProvider>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(DefaultSystemColors)));
+
+/// Installs the [`SystemColorProvider`] used to resolve system colors from now on, for every
+/// thread.
+pub fn set_system_color_provider<P: SystemColorProvider + 'static>(provider: P) {
+    *SYSTEM_COLOR_PROVIDER.write().unwrap() = Arc::new(provider);
+}
+
+/// Brings back the [`DefaultSystemColors`] provider.
+pub fn reset_system_color_provider() {
+    set_system_color_provider(DefaultSystemColors);
+}
+
+/// Returns the installed [`SystemColorProvider`].
+pub fn system_color_provider() -> Arc<dyn SystemColorProvider> {
+    SYSTEM_COLOR_PROVIDER.read().unwrap().clone()
+}
+
+thread_local! {
+    static SYSTEM_COLORS_DISABLED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` with system colors (and `accent`) rejected by the parser, for the colors that
+/// providers read themselves: resolving them would call the provider again, endlessly.
+pub(crate) fn without_system_colors<T>(f: impl FnOnce() -> T) -> T {
+    let disabled = SYSTEM_COLORS_DISABLED.replace(true);
+    let result = f();
+    SYSTEM_COLORS_DISABLED.set(disabled);
+    result
+}
+
+/// The installed [`SystemColorProvider`], or [`ErrorKind::InvalidSystemColor`] inside
+/// [`without_system_colors`].
+pub(crate) fn parser_system_color_provider(name: &str) -> Result<Arc<dyn SystemColorProvider>> {
+    if SYSTEM_COLORS_DISABLED.get() {
+        return Err(Error::new(
+            ErrorKind::InvalidSystemColor,
+            format!("{name} cannot be used in a system color"),
+        ));
+    }
+
+    Ok(system_color_provider())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{ParseOptions, parse_solid, parse_solid_with_options};
+
+    struct FakeProvider;
+
+    impl SystemColorProvider for FakeProvider {
+        fn system_color(&self, color: SystemColor, color_scheme: ColorScheme) -> Result<Solid> {
+            match (color, color_scheme) {
+                (SystemColor::AccentColor, ColorScheme::Light) => Solid::from_html("#1e66f5"),
+                (SystemColor::AccentColor, ColorScheme::Dark) => Solid::from_html("#89b4fa"),
+                (SystemColor::Canvas, _) => Solid::from_html("#1e1e2e"),
+                _ => Err(Error::new(ErrorKind::InvalidSystemColor, color.css_name())),
+            }
+        }
+    }
+
+    #[test]
+    fn test_system_color_names() {
+        assert_eq!(
+            SystemColor::try_from("CanvasText").unwrap(),
+            SystemColor::CanvasText
+        );
+        assert_eq!(
+            SystemColor::try_from("highlighttext").unwrap(),
+            SystemColor::HighlightText
+        );
+        assert_eq!(SystemColor::GrayText.to_string(), "GrayText");
+        assert!(SystemColor::try_from("Background").is_err());
+    }
+
+    #[test]
+    fn test_system_color_provider() {
+        // The provider is global, so everything that depends on it lives in this one test.
+        assert_eq!(
+            parse_solid("Canvas", None).unwrap().to_hex_string(),
+            "#ffffff"
+        );
+        assert_eq!(
+            parse_solid("canvastext", None).unwrap().to_hex_string(),
+            "#000000"
+        );
+
+        set_system_color_provider(FakeProvider);
+        let dark = ParseOptions::with_color_scheme(ColorScheme::Dark);
+
+        assert_eq!(
+            parse_solid("AccentColor", None).unwrap().to_hex_string(),
+            "#1e66f5"
+        );
+        assert_eq!(
+            parse_solid_with_options("accent", &dark)
+                .unwrap()
+                .to_hex_string(),
+            "#89b4fa"
+        );
+        assert_eq!(
+            parse_solid("accent_inactive", None).unwrap(),
+            inactive_accent(&Solid::from_html("#1e66f5").unwrap())
+        );
+        assert_eq!(
+            parse_solid("darken(Canvas, 10%)", None).unwrap(),
+            Solid::from_html("#1e1e2e").unwrap().darken(0.1)
+        );
+        assert_eq!(
+            parse_solid("LinkText", None).unwrap_err().kind(),
+            ErrorKind::InvalidSystemColor
+        );
+
+        reset_system_color_provider();
+        assert_eq!(
+            parse_solid_with_options("Canvas", &dark)
+                .unwrap()
+                .to_hex_string(),
+            "#121212"
+        );
+    }
+}
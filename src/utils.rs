@@ -3,11 +3,14 @@ use crate::Error;
 use crate::ErrorKind;
 use crate::Result;
 use std::path::{Component, Path, PathBuf};
+#[cfg(windows)]
 use windows_sys::Win32::Foundation::BOOL;
+#[cfg(windows)]
 use windows_sys::Win32::Foundation::FALSE;
+#[cfg(windows)]
 use windows_sys::Win32::Graphics::Dwm::DwmGetColorizationColor;
 
-use crate::{Hsla, Solid};
+use crate::{Colorspace, Hsla, Solid};
 
 fn hue_to_rgb(n1: f32, n2: f32, h: f32) -> f32 {
     let h = modulo(h, 6.0);
@@ -49,6 +52,30 @@ pub fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (f32, f32, f32) {
     (r, g, b)
 }
 
+// h = 0..360
+// w, b = 0..1
+// r, g, b = 0..1
+pub fn hwb_to_rgb(h: f32, w: f32, b: f32) -> (f32, f32, f32) {
+    if w + b >= 1.0 {
+        let gray = w / (w + b);
+        return (gray, gray, gray);
+    }
+
+    let (r, g, bl) = hsl_to_rgb(h, 1.0, 0.5);
+    let f = 1.0 - w - b;
+    (r * f + w, g * f + w, bl * f + w)
+}
+
+// r, g, b = 0..1
+// h = 0..360
+// w, b = 0..1
+pub fn rgb_to_hwb(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let (h, _, _) = rgb_to_hsl(r, g, b);
+    let w = r.min(g.min(b));
+    let bl = 1.0 - r.max(g.max(b));
+    (h, w, bl)
+}
+
 fn _get_min(rgb: &[f32]) -> f32 {
     rgb.iter().fold(f32::MAX, |a, &b| a.min(b))
 }
@@ -124,6 +151,464 @@ pub fn rgb_to_hsl(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
     (normalize_angle(h), s, l)
 }
 
+// x = 0..1 (gamma encoded sRGB), values outside the range are mirrored around zero
+pub fn srgb_to_linear(x: f32) -> f32 {
+    let abs = x.abs();
+    if abs < 0.04045 {
+        return x / 12.92;
+    }
+    ((abs + 0.055) / 1.055).powf(2.4).copysign(x)
+}
+
+// x = 0..1 (linear light sRGB), values outside the range are mirrored around zero
+pub fn linear_to_srgb(x: f32) -> f32 {
+    let abs = x.abs();
+    if abs < 0.0031308 {
+        return 12.92 * x;
+    }
+    (1.055 * abs.powf(1.0 / 2.4) - 0.055).copysign(x)
+}
+
+type Matrix3 = [[f64; 3]; 3];
+
+const LINEAR_SRGB_TO_XYZ_D65: Matrix3 = [
+    [0.41239079926595934, 0.357584339383878, 0.1804807884018343],
+    [0.21263900587151027, 0.715168678767756, 0.07219231536073371],
+    [0.01933081871559182, 0.11919477979462598, 0.9505321522496607],
+];
+
+const XYZ_D65_TO_LINEAR_SRGB: Matrix3 = [
+    [3.2409699419045226, -1.537383177570094, -0.4986107602930034],
+    [-0.9692436362808796, 1.8759675015077202, 0.04155505740717559],
+    [
+        0.05563007969699366,
+        -0.20397695888897652,
+        1.0569715142428786,
+    ],
+];
+
+// Bradford chromatic adaptation from the D65 to the D50 white point.
+const XYZ_D65_TO_D50: Matrix3 = [
+    [
+        1.0479298208405488,
+        0.022946793341019088,
+        -0.05019222954313557,
+    ],
+    [
+        0.029627815688159344,
+        0.990434484573249,
+        -0.01707382502938514,
+    ],
+    [
+        -0.009243058152591178,
+        0.015055144896577895,
+        0.7518742899580008,
+    ],
+];
+
+// Bradford chromatic adaptation from the D50 to the D65 white point.
+const XYZ_D50_TO_D65: Matrix3 = [
+    [
+        0.9554734527042182,
+        -0.023098536874261423,
+        0.0632593086610217,
+    ],
+    [
+        -0.028369706963208136,
+        1.0099954580058226,
+        0.021041398966943008,
+    ],
+    [
+        0.012314001688319899,
+        -0.020507696433477912,
+        1.3303659366080753,
+    ],
+];
+
+const D50_WHITE: [f64; 3] = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+
+const LAB_EPSILON: f64 = 216.0 / 24389.0;
+const LAB_KAPPA: f64 = 24389.0 / 27.0;
+
+fn multiply_matrix(m: &Matrix3, [x, y, z]: [f64; 3]) -> [f64; 3] {
+    [
+        m[0][0] * x + m[0][1] * y + m[0][2] * z,
+        m[1][0] * x + m[1][1] * y + m[1][2] * z,
+        m[2][0] * x + m[2][1] * y + m[2][2] * z,
+    ]
+}
+
+// r, g, b = 0..1 (gamma encoded sRGB)
+// x, y, z = CIE XYZ relative to D50
+pub fn rgb_to_xyz_d50(r: f32, g: f32, b: f32) -> [f64; 3] {
+    let linear = [r, g, b].map(|c| srgb_to_linear(c) as f64);
+    multiply_matrix(
+        &XYZ_D65_TO_D50,
+        multiply_matrix(&LINEAR_SRGB_TO_XYZ_D65, linear),
+    )
+}
+
+// x, y, z = CIE XYZ relative to D50
+// r, g, b = 0..1 (gamma encoded sRGB), not clamped to the sRGB gamut
+pub fn xyz_d50_to_rgb(xyz: [f64; 3]) -> (f32, f32, f32) {
+    let [r, g, b] = multiply_matrix(
+        &XYZ_D65_TO_LINEAR_SRGB,
+        multiply_matrix(&XYZ_D50_TO_D65, xyz),
+    )
+    .map(|c| linear_to_srgb(c as f32));
+    (r, g, b)
+}
+
+// x, y, z = CIE XYZ relative to D50
+// l = 0..100, a, b = roughly -125..125
+pub fn xyz_d50_to_lab(xyz: [f64; 3]) -> (f32, f32, f32) {
+    let [fx, fy, fz] = [0, 1, 2].map(|i| {
+        let v = xyz[i] / D50_WHITE[i];
+        if v > LAB_EPSILON {
+            v.cbrt()
+        } else {
+            (LAB_KAPPA * v + 16.0) / 116.0
+        }
+    });
+
+    (
+        (116.0 * fy - 16.0) as f32,
+        (500.0 * (fx - fy)) as f32,
+        (200.0 * (fy - fz)) as f32,
+    )
+}
+
+// l = 0..100, a, b = roughly -125..125
+// x, y, z = CIE XYZ relative to D50
+pub fn lab_to_xyz_d50(l: f32, a: f32, b: f32) -> [f64; 3] {
+    let (l, a, b) = (l as f64, a as f64, b as f64);
+    let fy = (l + 16.0) / 116.0;
+    let fx = a / 500.0 + fy;
+    let fz = fy - b / 200.0;
+
+    let x = if fx.powi(3) > LAB_EPSILON {
+        fx.powi(3)
+    } else {
+        (116.0 * fx - 16.0) / LAB_KAPPA
+    };
+    let y = if l > LAB_KAPPA * LAB_EPSILON {
+        fy.powi(3)
+    } else {
+        l / LAB_KAPPA
+    };
+    let z = if fz.powi(3) > LAB_EPSILON {
+        fz.powi(3)
+    } else {
+        (116.0 * fz - 16.0) / LAB_KAPPA
+    };
+
+    [x * D50_WHITE[0], y * D50_WHITE[1], z * D50_WHITE[2]]
+}
+
+// r, g, b = 0..1
+// l = 0..100, a, b = roughly -125..125
+pub fn rgb_to_lab(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    xyz_d50_to_lab(rgb_to_xyz_d50(r, g, b))
+}
+
+// l = 0..100, a, b = roughly -125..125
+// r, g, b = 0..1, not clamped to the sRGB gamut
+pub fn lab_to_rgb(l: f32, a: f32, b: f32) -> (f32, f32, f32) {
+    xyz_d50_to_rgb(lab_to_xyz_d50(l, a, b))
+}
+
+const LINEAR_SRGB_TO_LMS: Matrix3 = [
+    [0.4122214708, 0.5363325363, 0.0514459929],
+    [0.2119034982, 0.6806995451, 0.1073969566],
+    [0.0883024619, 0.2817188376, 0.6299787005],
+];
+
+const LMS_TO_OKLAB: Matrix3 = [
+    [0.2104542553, 0.7936177850, -0.0040720468],
+    [1.9779984951, -2.4285922050, 0.4505937099],
+    [0.0259040371, 0.7827717662, -0.8086757660],
+];
+
+const OKLAB_TO_LMS: Matrix3 = [
+    [1.0, 0.3963377774, 0.2158037573],
+    [1.0, -0.1055613458, -0.0638541728],
+    [1.0, -0.0894841775, -1.2914855480],
+];
+
+const LMS_TO_LINEAR_SRGB: Matrix3 = [
+    [4.0767416621, -3.3077115913, 0.2309699292],
+    [-1.2684380046, 2.6097574011, -0.3413193965],
+    [-0.0041960863, -0.7034186147, 1.7076147010],
+];
+
+// r, g, b = 0..1
+// l = 0..1, a, b = roughly -0.4..0.4
+pub fn rgb_to_oklab(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let linear = [r, g, b].map(|c| srgb_to_linear(c) as f64);
+    let lms = multiply_matrix(&LINEAR_SRGB_TO_LMS, linear).map(f64::cbrt);
+    let [l, a, b] = multiply_matrix(&LMS_TO_OKLAB, lms);
+    (l as f32, a as f32, b as f32)
+}
+
+// l = 0..1, a, b = roughly -0.4..0.4
+// r, g, b = 0..1, not clamped to the sRGB gamut
+pub fn oklab_to_rgb(l: f32, a: f32, b: f32) -> (f32, f32, f32) {
+    let lms = multiply_matrix(&OKLAB_TO_LMS, [l as f64, a as f64, b as f64]).map(|c| c.powi(3));
+    let [r, g, b] = multiply_matrix(&LMS_TO_LINEAR_SRGB, lms).map(|c| linear_to_srgb(c as f32));
+    (r, g, b)
+}
+
+// a, b = opponent axes of Lab-like color spaces
+// c = chroma, h = 0..360
+pub fn lab_to_lch(a: f32, b: f32) -> (f32, f32) {
+    let c = a.hypot(b);
+    let h = normalize_angle(b.atan2(a).to_degrees());
+    (c, h)
+}
+
+// c = chroma, h = 0..360
+// a, b = opponent axes of Lab-like color spaces
+pub fn lch_to_lab(c: f32, h: f32) -> (f32, f32) {
+    let (sin, cos) = h.to_radians().sin_cos();
+    (c * cos, c * sin)
+}
+
+const LINEAR_DISPLAY_P3_TO_XYZ_D65: Matrix3 = [
+    [0.4865709486482162, 0.26566769316909306, 0.1982172852343625],
+    [0.2289745640697488, 0.6917385218365064, 0.079286914093745],
+    [0.0, 0.04511338185890264, 1.043944368900976],
+];
+
+const XYZ_D65_TO_LINEAR_DISPLAY_P3: Matrix3 = [
+    [2.493496911941425, -0.9313836179191239, -0.40271078445071684],
+    [
+        -0.8294889695615747,
+        1.7626640603183463,
+        0.023624685841943577,
+    ],
+    [
+        0.03584583024378447,
+        -0.07617238926804182,
+        0.9568845240076872,
+    ],
+];
+
+const LINEAR_A98_RGB_TO_XYZ_D65: Matrix3 = [
+    [0.5766690429101305, 0.1855582379065463, 0.1882286462349947],
+    [0.29734497525053605, 0.6273635662554661, 0.07529145849399788],
+    [0.02703136138641234, 0.07068885253582723, 0.9913375368376388],
+];
+
+const XYZ_D65_TO_LINEAR_A98_RGB: Matrix3 = [
+    [
+        2.0415879038107465,
+        -0.5650069742788596,
+        -0.34473135077832956,
+    ],
+    [-0.9692436362808795, 1.8759675015077202, 0.04155505740717557],
+    [
+        0.013444280632031142,
+        -0.11836239223101838,
+        1.0151749943912054,
+    ],
+];
+
+// ProPhoto RGB is defined relative to the D50 white point.
+const LINEAR_PROPHOTO_RGB_TO_XYZ_D50: Matrix3 = [
+    [0.7977666449006423, 0.13518129740053308, 0.0313477341283922],
+    [0.2880748288194013, 0.711835234241873, 0.00008993693872564],
+    [0.0, 0.0, 0.8251046025104602],
+];
+
+const XYZ_D50_TO_LINEAR_PROPHOTO_RGB: Matrix3 = [
+    [
+        1.3457868816471583,
+        -0.25557208737979464,
+        -0.05110186497554526,
+    ],
+    [-0.5446307051249019, 1.5082477428451468, 0.02052744743642139],
+    [0.0, 0.0, 1.2119675456389452],
+];
+
+const LINEAR_REC2020_TO_XYZ_D65: Matrix3 = [
+    [0.6369580483012914, 0.14461690358620832, 0.1688809751641721],
+    [0.2627002120112671, 0.6779980715188708, 0.05930171646986196],
+    [0.0, 0.028072693049087428, 1.060985057710791],
+];
+
+const XYZ_D65_TO_LINEAR_REC2020: Matrix3 = [
+    [1.716651187971268, -0.355670783776392, -0.25336628137366],
+    [-0.666684351832489, 1.616481236634939, 0.0157685458139111],
+    [0.017639857445311, -0.042770613257809, 0.942103121235474],
+];
+
+const REC2020_ALPHA: f64 = 1.09929682680944;
+const REC2020_BETA: f64 = 0.018053968510807;
+
+fn a98_rgb_to_linear(x: f64) -> f64 {
+    x.abs().powf(563.0 / 256.0).copysign(x)
+}
+
+fn linear_to_a98_rgb(x: f64) -> f64 {
+    x.abs().powf(256.0 / 563.0).copysign(x)
+}
+
+fn prophoto_rgb_to_linear(x: f64) -> f64 {
+    if x.abs() <= 16.0 / 512.0 {
+        return x / 16.0;
+    }
+    x.abs().powf(1.8).copysign(x)
+}
+
+fn linear_to_prophoto_rgb(x: f64) -> f64 {
+    if x.abs() < 1.0 / 512.0 {
+        return x * 16.0;
+    }
+    x.abs().powf(1.0 / 1.8).copysign(x)
+}
+
+fn rec2020_to_linear(x: f64) -> f64 {
+    if x.abs() < REC2020_BETA * 4.5 {
+        return x / 4.5;
+    }
+    ((x.abs() + REC2020_ALPHA - 1.0) / REC2020_ALPHA)
+        .powf(1.0 / 0.45)
+        .copysign(x)
+}
+
+fn linear_to_rec2020(x: f64) -> f64 {
+    if x.abs() < REC2020_BETA {
+        return x * 4.5;
+    }
+    (REC2020_ALPHA * x.abs().powf(0.45) - (REC2020_ALPHA - 1.0)).copysign(x)
+}
+
+// c = the components of a color in `space`
+// x, y, z = CIE XYZ relative to D65
+fn colorspace_to_xyz_d65(space: Colorspace, c: [f32; 3]) -> [f64; 3] {
+    let c = c.map(|c| c as f64);
+    match space {
+        Colorspace::Srgb => multiply_matrix(
+            &LINEAR_SRGB_TO_XYZ_D65,
+            c.map(|c| srgb_to_linear(c as f32) as f64),
+        ),
+        Colorspace::SrgbLinear => multiply_matrix(&LINEAR_SRGB_TO_XYZ_D65, c),
+        Colorspace::DisplayP3 => multiply_matrix(
+            &LINEAR_DISPLAY_P3_TO_XYZ_D65,
+            c.map(|c| srgb_to_linear(c as f32) as f64),
+        ),
+        Colorspace::A98Rgb => multiply_matrix(&LINEAR_A98_RGB_TO_XYZ_D65, c.map(a98_rgb_to_linear)),
+        Colorspace::ProphotoRgb => multiply_matrix(
+            &XYZ_D50_TO_D65,
+            multiply_matrix(
+                &LINEAR_PROPHOTO_RGB_TO_XYZ_D50,
+                c.map(prophoto_rgb_to_linear),
+            ),
+        ),
+        Colorspace::Rec2020 => {
+            multiply_matrix(&LINEAR_REC2020_TO_XYZ_D65, c.map(rec2020_to_linear))
+        }
+        Colorspace::XyzD50 => multiply_matrix(&XYZ_D50_TO_D65, c),
+        Colorspace::XyzD65 => c,
+        _ => {
+            let (r, g, b) = colorspace_to_rgb(space, c.map(|c| c as f32));
+            colorspace_to_xyz_d65(Colorspace::Srgb, [r, g, b])
+        }
+    }
+}
+
+// x, y, z = CIE XYZ relative to D65
+// c = the components of the color in `space`
+fn xyz_d65_to_colorspace(space: Colorspace, xyz: [f64; 3]) -> [f32; 3] {
+    let c = match space {
+        Colorspace::Srgb => {
+            multiply_matrix(&XYZ_D65_TO_LINEAR_SRGB, xyz).map(|c| linear_to_srgb(c as f32) as f64)
+        }
+        Colorspace::SrgbLinear => multiply_matrix(&XYZ_D65_TO_LINEAR_SRGB, xyz),
+        Colorspace::DisplayP3 => multiply_matrix(&XYZ_D65_TO_LINEAR_DISPLAY_P3, xyz)
+            .map(|c| linear_to_srgb(c as f32) as f64),
+        Colorspace::A98Rgb => {
+            multiply_matrix(&XYZ_D65_TO_LINEAR_A98_RGB, xyz).map(linear_to_a98_rgb)
+        }
+        Colorspace::ProphotoRgb => multiply_matrix(
+            &XYZ_D50_TO_LINEAR_PROPHOTO_RGB,
+            multiply_matrix(&XYZ_D65_TO_D50, xyz),
+        )
+        .map(linear_to_prophoto_rgb),
+        Colorspace::Rec2020 => {
+            multiply_matrix(&XYZ_D65_TO_LINEAR_REC2020, xyz).map(linear_to_rec2020)
+        }
+        Colorspace::XyzD50 => multiply_matrix(&XYZ_D65_TO_D50, xyz),
+        Colorspace::XyzD65 => xyz,
+        _ => {
+            let [r, g, b] = xyz_d65_to_colorspace(Colorspace::Srgb, xyz);
+            return rgb_to_colorspace(space, r, g, b);
+        }
+    };
+    c.map(|c| c as f32)
+}
+
+// r, g, b = 0..1 (gamma encoded sRGB)
+// c = the components of the color in `space`, see `Colorspace` for their ranges
+pub fn rgb_to_colorspace(space: Colorspace, r: f32, g: f32, b: f32) -> [f32; 3] {
+    match space {
+        Colorspace::Srgb => [r, g, b],
+        Colorspace::Hsl => {
+            let (h, s, l) = rgb_to_hsl(r, g, b);
+            [h, s * 100.0, l * 100.0]
+        }
+        Colorspace::Hwb => {
+            let (h, w, b) = rgb_to_hwb(r, g, b);
+            [h, w * 100.0, b * 100.0]
+        }
+        Colorspace::Lab => {
+            let (l, a, b) = rgb_to_lab(r, g, b);
+            [l, a, b]
+        }
+        Colorspace::Lch => {
+            let (l, a, b) = rgb_to_lab(r, g, b);
+            let (c, h) = lab_to_lch(a, b);
+            [l, c, h]
+        }
+        Colorspace::Oklab => {
+            let (l, a, b) = rgb_to_oklab(r, g, b);
+            [l, a, b]
+        }
+        Colorspace::Oklch => {
+            let (l, a, b) = rgb_to_oklab(r, g, b);
+            let (c, h) = lab_to_lch(a, b);
+            [l, c, h]
+        }
+        _ => xyz_d65_to_colorspace(space, colorspace_to_xyz_d65(Colorspace::Srgb, [r, g, b])),
+    }
+}
+
+// c = the components of a color in `space`, see `Colorspace` for their ranges
+// r, g, b = 0..1 (gamma encoded sRGB), not clamped to the sRGB gamut
+pub fn colorspace_to_rgb(space: Colorspace, c: [f32; 3]) -> (f32, f32, f32) {
+    let [c1, c2, c3] = c;
+    match space {
+        Colorspace::Srgb => (c1, c2, c3),
+        Colorspace::Hsl => hsl_to_rgb(normalize_angle(c1), c2 / 100.0, c3 / 100.0),
+        Colorspace::Hwb => hwb_to_rgb(normalize_angle(c1), c2 / 100.0, c3 / 100.0),
+        Colorspace::Lab => lab_to_rgb(c1, c2, c3),
+        Colorspace::Lch => {
+            let (a, b) = lch_to_lab(c2.max(0.0), normalize_angle(c3));
+            lab_to_rgb(c1, a, b)
+        }
+        Colorspace::Oklab => oklab_to_rgb(c1, c2, c3),
+        Colorspace::Oklch => {
+            let (a, b) = lch_to_lab(c2.max(0.0), normalize_angle(c3));
+            oklab_to_rgb(c1, a, b)
+        }
+        _ => {
+            let [r, g, b] =
+                xyz_d65_to_colorspace(Colorspace::Srgb, colorspace_to_xyz_d65(space, c));
+            (r, g, b)
+        }
+    }
+}
+
 #[inline]
 pub fn normalize_angle(t: f32) -> f32 {
     let mut t = t % 360.0;
@@ -158,35 +643,100 @@ pub fn strip_string(input: String, prefixes: &[&str], suffix: char) -> String {
     result.strip_suffix(suffix).unwrap_or(&result).to_string()
 }
 
+/// Iterates over the top-level characters of `s` (outside of parentheses) for which
+/// `is_separator` is true, with their byte offsets.
+///
+/// A `)` closing no parenthesis of `s` is top-level, e.g. the end of `var(--a, f(x))` after
+/// `var(`.
+pub fn top_level_separators<'a>(
+    s: &'a str,
+    mut is_separator: impl FnMut(char) -> bool + 'a,
+) -> impl Iterator<Item = (usize, char)> + 'a {
+    let mut depth = 0usize;
+
+    s.char_indices().filter(move |&(_, c)| match c {
+        '(' => {
+            depth += 1;
+            false
+        }
+        ')' if depth > 0 => {
+            depth -= 1;
+            false
+        }
+        c => depth == 0 && is_separator(c),
+    })
+}
+
+/// Splits `s` on its top-level separators (see [`top_level_separators`]), keeping the
+/// separators nested inside parentheses (e.g. `darken(rgb(0, 0, 0), 10%)`) intact.
+///
+/// Items are not trimmed, and empty items are kept.
+pub fn split_top_level<'a>(
+    s: &'a str,
+    is_separator: impl FnMut(char) -> bool + 'a,
+) -> impl Iterator<Item = &'a str> + 'a {
+    let mut start = 0;
+
+    top_level_separators(s, is_separator)
+        .map(Some)
+        .chain([None])
+        .map(move |separator| match separator {
+            Some((i, c)) => {
+                let item = &s[start..i];
+                start = i + c.len_utf8();
+                item
+            }
+            None => &s[start..],
+        })
+}
+
+/// Splits the arguments of a color function on top-level commas.
+///
+/// Commas nested inside parentheses (e.g. `darken(rgb(0, 0, 0), 10%)`) are kept
+/// intact so that color arguments can themselves be color functions.
+pub fn split_args(s: &str) -> Vec<&str> {
+    split_top_level(s, |c| c == ',').map(str::trim).collect()
+}
+
+/// Splits a space separated list (e.g. `rgb(0 0 0) 30%`) on top-level whitespace.
+///
+/// Whitespace nested inside parentheses is kept intact, and empty items are skipped.
+pub fn split_words(s: &str) -> Vec<&str> {
+    split_top_level(s, char::is_whitespace)
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Splits the arguments of a color channel function (e.g. `rgb(0, 255, 0)` or
+/// `hsl(calc(120 + 45) 80% 50% / 50%)`) on top-level commas, slashes and whitespace.
+///
+/// Separators nested inside parentheses are kept intact, and empty items are skipped.
+pub fn split_params(s: &str) -> Vec<&str> {
+    split_top_level(s, |c| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|param| !param.is_empty())
+        .collect()
+}
+
+/// Returns the Desktop Window Manager colorization color, greyed out for inactive windows.
+#[cfg(windows)]
 pub fn get_accent(active: bool) -> Result<Solid> {
-    #[cfg(windows)]
-    {
-        let mut pcr_colorization: u32 = 0;
-        let mut pf_opaqueblend: BOOL = FALSE;
-        unsafe { DwmGetColorizationColor(&mut pcr_colorization, &mut pf_opaqueblend) };
-
-        let r = ((pcr_colorization & 0x00FF0000) >> 16) as f32 / 255.0;
-        let g = ((pcr_colorization & 0x0000FF00) >> 8) as f32 / 255.0;
-        let b = (pcr_colorization & 0x000000FF) as f32 / 255.0;
-        let avg = (r + g + b) / 3.0;
-
-        match active {
-            true => Ok(Solid::from([r, g, b, 1.0])),
-            false => Ok(Solid::from([
-                avg / 1.5 + r / 10.0,
-                avg / 1.5 + g / 10.0,
-                avg / 1.5 + b / 10.0,
-                1.0,
-            ])),
-        }
-    }
-
-    #[cfg(not(windows))]
-    {
-        Err(Error::new(
-            ErrorKind::InvalidFunction,
-            "accent is only available on windows platform",
-        ))
+    let mut pcr_colorization: u32 = 0;
+    let mut pf_opaqueblend: BOOL = FALSE;
+    unsafe { DwmGetColorizationColor(&mut pcr_colorization, &mut pf_opaqueblend) };
+
+    let r = ((pcr_colorization & 0x00FF0000) >> 16) as f32 / 255.0;
+    let g = ((pcr_colorization & 0x0000FF00) >> 8) as f32 / 255.0;
+    let b = (pcr_colorization & 0x000000FF) as f32 / 255.0;
+    let avg = (r + g + b) / 3.0;
+
+    match active {
+        true => Ok(Solid::from([r, g, b, 1.0])),
+        false => Ok(Solid::from([
+            avg / 1.5 + r / 10.0,
+            avg / 1.5 + g / 10.0,
+            avg / 1.5 + b / 10.0,
+            1.0,
+        ])),
     }
 }
 